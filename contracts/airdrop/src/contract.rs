@@ -0,0 +1,303 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use sha2::Digest;
+use std::convert::TryInto;
+
+use xdefi_token::airdrop::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestStageResponse,
+    MerkleRootResponse, MigrateMsg, QueryMsg,
+};
+use xdefi_token::staking::Cw20HookMsg as StakingCw20HookMsg;
+
+use crate::state::{
+    read_claimed, read_config, read_latest_stage, read_merkle_root, store_claimed, store_config,
+    store_latest_stage, store_merkle_root, Config,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_canonicalize(&msg.owner)?,
+        anchor_token: deps.api.addr_canonicalize(&msg.anchor_token)?,
+        staking_contract: msg
+            .staking_contract
+            .map(|addr| deps.api.addr_canonicalize(&addr))
+            .transpose()?,
+    };
+    store_config(deps.storage, &config)?;
+
+    let stage: u8 = 0;
+    store_latest_stage(deps.storage, stage)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            staking_contract,
+        } => update_config(deps, info, owner, staking_contract),
+        ExecuteMsg::RegisterMerkleRoot { merkle_root } => {
+            register_merkle_root(deps, info, merkle_root)
+        }
+        ExecuteMsg::Claim {
+            stage,
+            amount,
+            proof,
+        } => claim(deps, info, stage, amount, proof),
+        ExecuteMsg::ClaimAndStake {
+            stage,
+            amount,
+            proof,
+            pool_id,
+        } => claim_and_stake(deps, info, stage, amount, proof, pool_id),
+    }
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    staking_contract: Option<String>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(&owner)?;
+    }
+
+    if let Some(staking_contract) = staking_contract {
+        config.staking_contract = Some(deps.api.addr_canonicalize(&staking_contract)?);
+    }
+
+    store_config(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn register_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    merkle_root: String,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    match hex::decode_to_slice(&merkle_root, &mut root_buf) {
+        Ok(()) => {}
+        Err(e) => return Err(StdError::generic_err(format!("Invalid merkle root: {}", e))),
+    }
+
+    let latest_stage: u8 = read_latest_stage(deps.storage)?;
+    let stage = latest_stage
+        .checked_add(1)
+        .ok_or_else(|| StdError::generic_err("No more stages can be registered"))?;
+
+    store_merkle_root(deps.storage, stage, merkle_root.to_string())?;
+    store_latest_stage(deps.storage, stage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_merkle_root"),
+        attr("stage", stage.to_string()),
+        attr("merkle_root", merkle_root),
+    ]))
+}
+
+pub fn claim(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    verify_and_store_claim(deps.branch(), &info, stage, amount, proof)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.anchor_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+        }))
+        .add_attributes(vec![
+            attr("action", "claim"),
+            attr("stage", stage.to_string()),
+            attr("address", info.sender.as_str()),
+            attr("amount", amount.to_string()),
+        ]))
+}
+
+/// Claim and send the tokens to the staking contract `Bond` hook,
+/// bonding them on behalf of the claimer
+pub fn claim_and_stake(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+    pool_id: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let staking_contract = match config.staking_contract {
+        Some(staking_contract) => staking_contract,
+        None => return Err(StdError::generic_err("Staking contract is not registered")),
+    };
+
+    verify_and_store_claim(deps.branch(), &info, stage, amount, proof)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.anchor_token)?.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: deps.api.addr_humanize(&staking_contract)?.to_string(),
+                amount,
+                msg: to_binary(&StakingCw20HookMsg::Bond {
+                    pool_id,
+                    staker: Some(info.sender.to_string()),
+                })?,
+            })?,
+        }))
+        .add_attributes(vec![
+            attr("action", "claim_and_stake"),
+            attr("stage", stage.to_string()),
+            attr("address", info.sender.as_str()),
+            attr("amount", amount.to_string()),
+        ]))
+}
+
+// verify the merkle proof of the claim and mark it as claimed
+fn verify_and_store_claim(
+    deps: DepsMut,
+    info: &MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<()> {
+    let merkle_root: String = read_merkle_root(deps.storage, stage)?;
+
+    let user_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // If user claimed target stage, return err
+    if read_claimed(deps.storage, &user_raw, stage)? {
+        return Err(StdError::generic_err("already claimed"));
+    }
+
+    let user_input: String = info.sender.to_string() + &amount.to_string();
+    let mut hash: [u8; 32] = sha2::Sha256::digest(user_input.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| StdError::generic_err("Wrong length"))?;
+
+    for p in proof {
+        let mut proof_buf: [u8; 32] = [0; 32];
+        match hex::decode_to_slice(p, &mut proof_buf) {
+            Ok(()) => {}
+            _ => return Err(StdError::generic_err("Invalid hex encoded proof")),
+        }
+
+        // the pair is sorted before hashing, so proofs don't carry the sibling side
+        hash = if hash < proof_buf {
+            sha2::Sha256::digest(&[hash, proof_buf].concat())
+                .as_slice()
+                .try_into()
+                .map_err(|_| StdError::generic_err("Wrong length"))?
+        } else {
+            sha2::Sha256::digest(&[proof_buf, hash].concat())
+                .as_slice()
+                .try_into()
+                .map_err(|_| StdError::generic_err("Wrong length"))?
+        };
+    }
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    match hex::decode_to_slice(merkle_root, &mut root_buf) {
+        Ok(()) => {}
+        _ => return Err(StdError::generic_err("Invalid hex encoded merkle root")),
+    }
+
+    if root_buf != hash {
+        return Err(StdError::generic_err("Verification is failed"));
+    }
+
+    // Update claim index to the current stage
+    store_claimed(deps.storage, &user_raw, stage)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::MerkleRoot { stage } => to_binary(&query_merkle_root(deps, stage)?),
+        QueryMsg::LatestStage {} => to_binary(&query_latest_stage(deps)?),
+        QueryMsg::IsClaimed { stage, address } => to_binary(&query_claimed(deps, stage, address)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?.to_string(),
+        anchor_token: deps.api.addr_humanize(&state.anchor_token)?.to_string(),
+        staking_contract: state
+            .staking_contract
+            .map(|addr| deps.api.addr_humanize(&addr).map(|addr| addr.to_string()))
+            .transpose()?,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_merkle_root(deps: Deps, stage: u8) -> StdResult<MerkleRootResponse> {
+    let merkle_root = read_merkle_root(deps.storage, stage)?;
+    let resp = MerkleRootResponse { stage, merkle_root };
+
+    Ok(resp)
+}
+
+pub fn query_latest_stage(deps: Deps) -> StdResult<LatestStageResponse> {
+    let latest_stage = read_latest_stage(deps.storage)?;
+    let resp = LatestStageResponse { latest_stage };
+
+    Ok(resp)
+}
+
+pub fn query_claimed(deps: Deps, stage: u8, address: String) -> StdResult<IsClaimedResponse> {
+    let user_raw = deps.api.addr_canonicalize(&address)?;
+    let resp = IsClaimedResponse {
+        is_claimed: read_claimed(deps.storage, &user_raw, stage)?,
+    };
+
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
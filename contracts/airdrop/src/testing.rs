@@ -0,0 +1,318 @@
+use crate::contract::{execute, instantiate, query};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use xdefi_token::airdrop::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestStageResponse,
+    MerkleRootResponse, QueryMsg,
+};
+use xdefi_token::staking::Cw20HookMsg as StakingCw20HookMsg;
+
+// leaves are sha256(address + amount), pairs are sorted before hashing
+const STAGE_1_ROOT: &str = "38f28ca1fd39c693aa163b5d6edccb46db3a99cd8d860753d3a712d2ada136aa";
+const STAGE_2_ROOT: &str = "e9d6b4f8d081df0b44cb3bffaf9928484b679e8edfe61515394c4d83627935d6";
+
+fn instantiate_msg(staking_contract: Option<String>) -> InstantiateMsg {
+    InstantiateMsg {
+        owner: "owner0000".to_string(),
+        anchor_token: "xdefi0000".to_string(),
+        staking_contract,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg(None)).unwrap();
+
+    // it worked, let's query the state
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: "owner0000".to_string(),
+            anchor_token: "xdefi0000".to_string(),
+            staking_contract: None,
+        }
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::LatestStage {}).unwrap();
+    let latest_stage: LatestStageResponse = from_binary(&res).unwrap();
+    assert_eq!(0u8, latest_stage.latest_stage);
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg(None)).unwrap();
+
+    // update owner
+    let info = mock_info("owner0000", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("owner0001".to_string()),
+        staking_contract: Some("staking0000".to_string()),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    // it worked, let's query the state
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!("owner0001", config.owner.as_str());
+    assert_eq!(Some("staking0000".to_string()), config.staking_contract);
+
+    // Unauthorized err
+    let info = mock_info("owner0000", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking_contract: None,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+}
+
+#[test]
+fn register_merkle_root() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg(None)).unwrap();
+
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: STAGE_1_ROOT.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // the root must be a hex encoded sha256 hash
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: "634de21cde".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg);
+    assert!(res.is_err());
+
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: STAGE_1_ROOT.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "register_merkle_root"),
+            attr("stage", "1"),
+            attr("merkle_root", STAGE_1_ROOT),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::LatestStage {}).unwrap();
+    let latest_stage: LatestStageResponse = from_binary(&res).unwrap();
+    assert_eq!(1u8, latest_stage.latest_stage);
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::MerkleRoot {
+            stage: latest_stage.latest_stage,
+        },
+    )
+    .unwrap();
+    let merkle_root: MerkleRootResponse = from_binary(&res).unwrap();
+    assert_eq!(STAGE_1_ROOT.to_string(), merkle_root.merkle_root);
+}
+
+#[test]
+fn claim() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg(None)).unwrap();
+
+    // Register merkle roots
+    let info = mock_info("owner0000", &[]);
+    for merkle_root in [STAGE_1_ROOT, STAGE_2_ROOT].iter() {
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: merkle_root.to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::Claim {
+        amount: Uint128::from(2000u128),
+        stage: 1u8,
+        proof: vec![
+            "2e3d98c7efa02a4f0798dd84cf1191b77f1b9e98d8eea2cd2bf71fad2c71ba2e".to_string(),
+            "bfe88b2362220aacd28b356558a33346dac5520bac894c51196dc7e7d6d8f46d".to_string(),
+        ],
+    };
+
+    // the proof does not match another address
+    let info = mock_info("addr0002", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(res, Err(StdError::generic_err("Verification is failed")));
+
+    let info = mock_info("addr0001", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xdefi0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(2000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim"),
+            attr("stage", "1"),
+            attr("address", "addr0001"),
+            attr("amount", "2000"),
+        ]
+    );
+
+    let is_claimed = |stage: u8, address: &str| -> bool {
+        from_binary::<IsClaimedResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::IsClaimed {
+                    stage,
+                    address: address.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .is_claimed
+    };
+    assert!(is_claimed(1, "addr0001"));
+    assert!(!is_claimed(2, "addr0001"));
+    assert!(!is_claimed(1, "addr0000"));
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    assert_eq!(res, Err(StdError::generic_err("already claimed")));
+
+    // claims are tracked per stage
+    let msg = ExecuteMsg::Claim {
+        amount: Uint128::from(600u128),
+        stage: 2u8,
+        proof: vec!["070c083731f8d2e2a5cd9a5cfcd0c686c6a19fbb44ea5cb4cf5cde0a207ef0fe".to_string()],
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim"),
+            attr("stage", "2"),
+            attr("address", "addr0001"),
+            attr("amount", "600"),
+        ]
+    );
+
+    // the amount is part of the leaf
+    let msg = ExecuteMsg::Claim {
+        amount: Uint128::from(5000u128),
+        stage: 1u8,
+        proof: vec![
+            "9252eb9b10698e2ddc5894023e9663951f57cbd49f6eefd63063e1a017aa11fd".to_string(),
+            "fb3b7c11ec3c75d8a8259c1242c158fb1dd1a71ceaaad5dac71cc5b75d1eca15".to_string(),
+        ],
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0002", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("Verification is failed")));
+}
+
+#[test]
+fn claim_and_stake() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg(None)).unwrap();
+
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: STAGE_1_ROOT.to_string(),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ClaimAndStake {
+        amount: Uint128::from(3000u128),
+        stage: 1u8,
+        proof: vec![
+            "904abed19f36235ba8c9a98bdbefce57601a1e627e6e066dc69a1bafd9f3fdaa".to_string(),
+            "fb3b7c11ec3c75d8a8259c1242c158fb1dd1a71ceaaad5dac71cc5b75d1eca15".to_string(),
+        ],
+        pool_id: None,
+    };
+    let info = mock_info("addr0002", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Staking contract is not registered"))
+    );
+
+    let update_msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        staking_contract: Some("staking0000".to_string()),
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        update_msg,
+    )
+    .unwrap();
+
+    // the tokens are bonded for the claimer
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xdefi0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0000".to_string(),
+                amount: Uint128::from(3000u128),
+                msg: to_binary(&StakingCw20HookMsg::Bond {
+                    pool_id: None,
+                    staker: Some("addr0002".to_string()),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim_and_stake"),
+            attr("stage", "1"),
+            attr("address", "addr0002"),
+            attr("amount", "3000"),
+        ]
+    );
+
+    // claiming and staking counts as the stage claim
+    let msg = ExecuteMsg::Claim {
+        amount: Uint128::from(3000u128),
+        stage: 1u8,
+        proof: vec![
+            "904abed19f36235ba8c9a98bdbefce57601a1e627e6e066dc69a1bafd9f3fdaa".to_string(),
+            "fb3b7c11ec3c75d8a8259c1242c158fb1dd1a71ceaaad5dac71cc5b75d1eca15".to_string(),
+        ],
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0002", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("already claimed")));
+}
@@ -0,0 +1,253 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_binary, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use xdefi_token::asset::AssetInfo;
+use xdefi_token::collector::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use xdefi_token::querier::{deduct_tax, query_balance, query_token_balance};
+
+use crate::state::{read_config, read_tmp_balance, store_config, store_tmp_balance, Config};
+use crate::terraswap::{query_pair_info, Asset, PairExecuteMsg};
+
+// reply id of the swap sub message, the swapped tokens are distributed on success
+const SWEEP_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    validate_reward_factor(msg.reward_factor)?;
+    if let Some(max_spread) = msg.max_spread {
+        validate_max_spread(max_spread)?;
+    }
+
+    store_config(
+        deps.storage,
+        &Config {
+            gov_contract: deps.api.addr_canonicalize(&msg.gov_contract)?,
+            terraswap_factory: deps.api.addr_canonicalize(&msg.terraswap_factory)?,
+            anchor_token: deps.api.addr_canonicalize(&msg.anchor_token)?,
+            distributor_contract: deps.api.addr_canonicalize(&msg.distributor_contract)?,
+            reward_factor: msg.reward_factor,
+            max_spread: msg.max_spread,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            reward_factor,
+            max_spread,
+        } => update_config(deps, info, reward_factor, max_spread),
+        ExecuteMsg::Sweep { denom } => sweep(deps, env, denom),
+    }
+}
+
+fn validate_reward_factor(reward_factor: Decimal) -> StdResult<()> {
+    if reward_factor > Decimal::one() {
+        Err(StdError::generic_err(
+            "reward_factor must be smaller than or equal to 1",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_max_spread(max_spread: Decimal) -> StdResult<()> {
+    if max_spread > Decimal::one() {
+        Err(StdError::generic_err(
+            "max_spread must be smaller than or equal to 1",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_factor: Option<Decimal>,
+    max_spread: Option<Decimal>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.gov_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(reward_factor) = reward_factor {
+        validate_reward_factor(reward_factor)?;
+        config.reward_factor = reward_factor;
+    }
+
+    if let Some(max_spread) = max_spread {
+        validate_max_spread(max_spread)?;
+        config.max_spread = Some(max_spread);
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Sweep
+/// Anyone can execute sweep function to swap
+/// the collected `denom` balance to the anchor token, bounded by max_spread;
+/// the swapped tokens are distributed from the reply
+pub fn sweep(deps: DepsMut, env: Env, denom: String) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let anchor_token = deps.api.addr_humanize(&config.anchor_token)?;
+    let terraswap_factory = deps.api.addr_humanize(&config.terraswap_factory)?;
+
+    let pair_info = query_pair_info(
+        deps.as_ref(),
+        &terraswap_factory,
+        &[
+            AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: anchor_token.to_string(),
+            },
+        ],
+    )?;
+
+    let amount: Uint128 = query_balance(
+        deps.as_ref(),
+        env.contract.address.clone(),
+        denom.to_string(),
+    )?
+    .into();
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to sweep"));
+    }
+
+    // the swap amount is sent with the message, so the tax has to be paid out of it
+    let swap_coin = deduct_tax(deps.as_ref(), Coin { denom, amount })?;
+
+    // anchor tokens sent to the collector directly are left out of the distribution
+    let balance: Uint128 =
+        query_token_balance(deps.as_ref(), anchor_token, env.contract.address)?.into();
+    store_tmp_balance(deps.storage, &balance)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: pair_info.contract_addr,
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: swap_coin.denom.to_string(),
+                        },
+                        amount: swap_coin.amount,
+                    },
+                    belief_price: None,
+                    max_spread: config.max_spread,
+                    to: None,
+                })?,
+                funds: vec![swap_coin.clone()],
+            }),
+            SWEEP_REPLY_ID,
+        ))
+        .add_attributes(vec![
+            attr("action", "sweep"),
+            attr("denom", swap_coin.denom),
+            attr("amount", swap_coin.amount.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+    if msg.id == SWEEP_REPLY_ID {
+        return distribute(deps, env);
+    }
+
+    Err(StdError::generic_err("invalid reply id"))
+}
+
+// split the anchor tokens received from the swap between gov and the distributor by reward_factor
+fn distribute(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let anchor_token = deps.api.addr_humanize(&config.anchor_token)?;
+
+    let balance: Uint128 =
+        query_token_balance(deps.as_ref(), anchor_token.clone(), env.contract.address)?.into();
+    let amount = balance.checked_sub(read_tmp_balance(deps.storage)?)?;
+    let gov_amount = amount * config.reward_factor;
+    let distributor_amount = amount.checked_sub(gov_amount)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !gov_amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: deps.api.addr_humanize(&config.gov_contract)?.to_string(),
+                amount: gov_amount,
+            })?,
+        }));
+    }
+
+    if !distributor_amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: deps
+                    .api
+                    .addr_humanize(&config.distributor_contract)?
+                    .to_string(),
+                amount: distributor_amount,
+            })?,
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "distribute"),
+        attr("gov_amount", gov_amount.to_string()),
+        attr("distributor_amount", distributor_amount.to_string()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        gov_contract: deps.api.addr_humanize(&state.gov_contract)?.to_string(),
+        terraswap_factory: deps
+            .api
+            .addr_humanize(&state.terraswap_factory)?
+            .to_string(),
+        anchor_token: deps.api.addr_humanize(&state.anchor_token)?.to_string(),
+        distributor_contract: deps
+            .api
+            .addr_humanize(&state.distributor_contract)?
+            .to_string(),
+        reward_factor: state.reward_factor,
+        max_spread: state.max_spread,
+    };
+
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
@@ -0,0 +1,8 @@
+pub mod contract;
+pub mod state;
+pub mod terraswap;
+
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
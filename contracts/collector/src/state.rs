@@ -0,0 +1,38 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage, Uint128};
+use cosmwasm_storage::{singleton, singleton_read};
+
+static KEY_CONFIG: &[u8] = b"config";
+static KEY_TMP_BALANCE: &[u8] = b"tmp_balance";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub gov_contract: CanonicalAddr, // collected rewards receiver
+    pub terraswap_factory: CanonicalAddr,
+    pub anchor_token: CanonicalAddr,
+    pub distributor_contract: CanonicalAddr,
+    /// share of the swapped tokens sent to gov, the rest goes to the distributor
+    pub reward_factor: Decimal,
+    /// max spread of the sweep swaps, terraswap applies its default when none
+    #[serde(default)]
+    pub max_spread: Option<Decimal>,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+/// anchor token balance held before the sweep swap, only the difference is distributed
+pub fn store_tmp_balance(storage: &mut dyn Storage, balance: &Uint128) -> StdResult<()> {
+    singleton(storage, KEY_TMP_BALANCE).save(balance)
+}
+
+pub fn read_tmp_balance(storage: &dyn Storage) -> StdResult<Uint128> {
+    singleton_read(storage, KEY_TMP_BALANCE).load()
+}
@@ -0,0 +1,51 @@
+//! Minimal subset of the Terraswap factory and pair interfaces
+//! used by the collector to find the pair and swap the collected denom.
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Decimal, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
+
+use xdefi_token::asset::AssetInfo;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairInfo {
+    pub asset_infos: [AssetInfo; 2],
+    pub contract_addr: String,
+    pub liquidity_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FactoryQueryMsg {
+    Pair { asset_infos: [AssetInfo; 2] },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairExecuteMsg {
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+pub fn query_pair_info(
+    deps: Deps,
+    factory_contract: &Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: factory_contract.to_string(),
+        msg: to_binary(&FactoryQueryMsg::Pair {
+            asset_infos: asset_infos.clone(),
+        })?,
+    }))
+}
@@ -0,0 +1,313 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use crate::terraswap::{Asset, PairExecuteMsg};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Coin, ContractResult, CosmosMsg, Decimal, OwnedDeps, Reply,
+    ReplyOn, StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use xdefi_token::asset::AssetInfo;
+use xdefi_token::collector::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+type MockDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        terraswap_factory: "terraswapfactory".to_string(),
+        gov_contract: "gov0000".to_string(),
+        anchor_token: "tokenXDEFI".to_string(),
+        distributor_contract: "distributor0000".to_string(),
+        reward_factor: Decimal::percent(90),
+        max_spread: Some(Decimal::percent(5)),
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // it worked, let's query the state
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            terraswap_factory: "terraswapfactory".to_string(),
+            gov_contract: "gov0000".to_string(),
+            anchor_token: "tokenXDEFI".to_string(),
+            distributor_contract: "distributor0000".to_string(),
+            reward_factor: Decimal::percent(90),
+            max_spread: Some(Decimal::percent(5)),
+        }
+    );
+
+    // reward factor bigger than 1 is not allowed
+    let mut msg = instantiate_msg();
+    msg.reward_factor = Decimal::percent(101);
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "reward_factor must be smaller than or equal to 1"
+        ))
+    );
+
+    let mut msg = instantiate_msg();
+    msg.max_spread = Some(Decimal::percent(101));
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "max_spread must be smaller than or equal to 1"
+        ))
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // only the gov contract can update the config
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_factor: Some(Decimal::percent(80)),
+        max_spread: Some(Decimal::percent(10)),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(Decimal::percent(80), config.reward_factor);
+    assert_eq!(Some(Decimal::percent(10)), config.max_spread);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_factor: Some(Decimal::percent(120)),
+        max_spread: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "reward_factor must be smaller than or equal to 1"
+        ))
+    );
+}
+
+#[test]
+fn test_sweep() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    deps.querier.with_token_balances(&[(
+        &"tokenXDEFI".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::zero())],
+    )]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // no pair for the denom
+    let msg = ExecuteMsg::Sweep {
+        denom: "uusd".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+
+    deps.querier
+        .with_terraswap_pairs(&[(&"uusdtokenXDEFI".to_string(), &"pairXDEFI".to_string())]);
+
+    // nothing collected for the denom
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Sweep {
+            denom: "ukrw".to_string(),
+        },
+    );
+    assert!(res.is_err());
+
+    deps.querier.with_terraswap_pairs(&[
+        (&"uusdtokenXDEFI".to_string(), &"pairXDEFI".to_string()),
+        (&"ukrwtokenXDEFI".to_string(), &"pairKRW".to_string()),
+    ]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Sweep {
+            denom: "ukrw".to_string(),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("Nothing to sweep")));
+
+    // anyone can sweep, the tax is deducted from the swap amount
+    // and the swap is bounded by the configured max spread
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg {
+            msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pairXDEFI".to_string(),
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: "uusd".to_string()
+                        },
+                        amount: Uint128::from(990100u128),
+                    },
+                    max_spread: Some(Decimal::percent(5)),
+                    belief_price: None,
+                    to: None,
+                })
+                .unwrap(),
+                funds: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(990100u128),
+                }],
+            }),
+            gas_limit: None,
+            id: 1,
+            reply_on: ReplyOn::Success,
+        }]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "sweep"),
+            attr("denom", "uusd"),
+            attr("amount", "990100"),
+        ]
+    );
+}
+
+#[test]
+fn test_distribute() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+    deps.querier
+        .with_terraswap_pairs(&[(&"uusdtokenXDEFI".to_string(), &"pairXDEFI".to_string())]);
+    let set_anchor_balance = |deps: &mut MockDeps, balance: u128| {
+        deps.querier.with_token_balances(&[(
+            &"tokenXDEFI".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(balance))],
+        )]);
+    };
+    let sweep_and_reply = |deps: &mut MockDeps, balance_before: u128, balance_after: u128| {
+        set_anchor_balance(deps, balance_before);
+        let msg = ExecuteMsg::Sweep {
+            denom: "uusd".to_string(),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+        // the swap credits the collector before the reply
+        set_anchor_balance(deps, balance_after);
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 1,
+                result: ContractResult::Ok(SubMsgExecutionResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap()
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // only the swapped tokens are distributed, the 20 held before stay
+    let res = sweep_and_reply(&mut deps, 20, 120);
+
+    // 90% goes to gov, the rest to the distributor
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "tokenXDEFI".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "gov0000".to_string(),
+                    amount: Uint128::from(90u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "tokenXDEFI".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "distributor0000".to_string(),
+                    amount: Uint128::from(10u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "distribute"),
+            attr("gov_amount", "90"),
+            attr("distributor_amount", "10"),
+        ]
+    );
+
+    // the whole swap goes to gov with a reward factor of 1
+    let msg = ExecuteMsg::UpdateConfig {
+        reward_factor: Some(Decimal::one()),
+        max_spread: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    let res = sweep_and_reply(&mut deps, 120, 220);
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "tokenXDEFI".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "gov0000".to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
@@ -0,0 +1,121 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use xdefi_token::community::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+use crate::state::{read_config, store_config, Config};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    store_config(
+        deps.storage,
+        &Config {
+            gov_contract: deps.api.addr_canonicalize(&msg.gov_contract)?,
+            anchor_token: deps.api.addr_canonicalize(&msg.anchor_token)?,
+            spend_limit: msg.spend_limit,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { spend_limit } => update_config(deps, info, spend_limit),
+        ExecuteMsg::Spend { recipient, amount } => spend(deps, info, recipient, amount),
+    }
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    spend_limit: Option<Uint128>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    if config.gov_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(spend_limit) = spend_limit {
+        config.spend_limit = spend_limit;
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Spend
+/// Owner can execute spend operation to send
+/// `amount` of anchor token to `recipient` for community purpose
+pub fn spend(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    if config.gov_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if config.spend_limit < amount {
+        return Err(StdError::generic_err("Cannot spend more than spend_limit"));
+    }
+
+    let anchor_token = deps.api.addr_humanize(&config.anchor_token)?.to_string();
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token,
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.clone(),
+                amount,
+            })?,
+        }))
+        .add_attributes(vec![
+            attr("action", "spend"),
+            attr("recipient", recipient),
+            attr("amount", amount.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        gov_contract: deps.api.addr_humanize(&state.gov_contract)?.to_string(),
+        anchor_token: deps.api.addr_humanize(&state.anchor_token)?.to_string(),
+        spend_limit: state.spend_limit,
+    };
+
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
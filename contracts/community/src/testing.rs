@@ -0,0 +1,115 @@
+use crate::contract::{execute, instantiate, query};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, to_binary, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use xdefi_token::community::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        gov_contract: "gov0000".to_string(),
+        anchor_token: "xdefi0000".to_string(),
+        spend_limit: Uint128::from(1000000u128),
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+
+    // we can just call .unwrap() to assert this was a success
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // it worked, let's query the state
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            gov_contract: "gov0000".to_string(),
+            anchor_token: "xdefi0000".to_string(),
+            spend_limit: Uint128::from(1000000u128),
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // only the gov contract can update the config
+    let msg = ExecuteMsg::UpdateConfig {
+        spend_limit: Some(Uint128::from(500000u128)),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::from(500000u128), config.spend_limit);
+}
+
+#[test]
+fn test_spend() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // permission failed
+    let msg = ExecuteMsg::Spend {
+        recipient: "addr0000".to_string(),
+        amount: Uint128::from(1000000u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // failed due to spend limit
+    let msg = ExecuteMsg::Spend {
+        recipient: "addr0000".to_string(),
+        amount: Uint128::from(2000000u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Cannot spend more than spend_limit"))
+    );
+
+    let msg = ExecuteMsg::Spend {
+        recipient: "addr0000".to_string(),
+        amount: Uint128::from(1000000u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xdefi0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(1000000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "spend"),
+            attr("recipient", "addr0000"),
+            attr("amount", "1000000"),
+        ]
+    );
+}
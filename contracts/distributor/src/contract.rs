@@ -0,0 +1,189 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use xdefi_token::distributor::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+use crate::state::{read_config, store_config, Config};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let whitelist = msg
+        .whitelist
+        .into_iter()
+        .map(|w| deps.api.addr_canonicalize(&w))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    store_config(
+        deps.storage,
+        &Config {
+            gov_contract: deps.api.addr_canonicalize(&msg.gov_contract)?,
+            anchor_token: deps.api.addr_canonicalize(&msg.anchor_token)?,
+            whitelist,
+            spend_limit: msg.spend_limit,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { spend_limit } => update_config(deps, info, spend_limit),
+        ExecuteMsg::Spend { recipient, amount } => spend(deps, info, recipient, amount),
+        ExecuteMsg::AddDistributor { distributor } => add_distributor(deps, info, distributor),
+        ExecuteMsg::RemoveDistributor { distributor } => {
+            remove_distributor(deps, info, distributor)
+        }
+    }
+}
+
+fn assert_gov_contract(deps: Deps, info: &MessageInfo, config: &Config) -> StdResult<()> {
+    if config.gov_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    Ok(())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    spend_limit: Option<Uint128>,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    assert_gov_contract(deps.as_ref(), &info, &config)?;
+
+    if let Some(spend_limit) = spend_limit {
+        config.spend_limit = spend_limit;
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn add_distributor(
+    deps: DepsMut,
+    info: MessageInfo,
+    distributor: String,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    assert_gov_contract(deps.as_ref(), &info, &config)?;
+
+    let distributor_raw = deps.api.addr_canonicalize(&distributor)?;
+    if config.whitelist.contains(&distributor_raw) {
+        return Err(StdError::generic_err("Distributor already registered"));
+    }
+
+    config.whitelist.push(distributor_raw);
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_distributor"),
+        attr("distributor", distributor),
+    ]))
+}
+
+pub fn remove_distributor(
+    deps: DepsMut,
+    info: MessageInfo,
+    distributor: String,
+) -> StdResult<Response> {
+    let mut config: Config = read_config(deps.storage)?;
+    assert_gov_contract(deps.as_ref(), &info, &config)?;
+
+    let distributor_raw = deps.api.addr_canonicalize(&distributor)?;
+    let whitelist_len = config.whitelist.len();
+    config.whitelist.retain(|w| *w != distributor_raw);
+    if whitelist_len == config.whitelist.len() {
+        return Err(StdError::generic_err("Distributor not found"));
+    }
+
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_distributor"),
+        attr("distributor", distributor),
+    ]))
+}
+
+/// Spend
+/// Whitelisted contracts can execute spend operation
+/// to send anchor token to recipient
+pub fn spend(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !config.whitelist.contains(&sender_raw) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if config.spend_limit < amount {
+        return Err(StdError::generic_err("Cannot spend more than spend_limit"));
+    }
+
+    let anchor_token = deps.api.addr_humanize(&config.anchor_token)?.to_string();
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token,
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.clone(),
+                amount,
+            })?,
+        }))
+        .add_attributes(vec![
+            attr("action", "spend"),
+            attr("recipient", recipient),
+            attr("amount", amount.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        gov_contract: deps.api.addr_humanize(&state.gov_contract)?.to_string(),
+        anchor_token: deps.api.addr_humanize(&state.anchor_token)?.to_string(),
+        whitelist: state
+            .whitelist
+            .into_iter()
+            .map(|w| deps.api.addr_humanize(&w).map(|addr| addr.to_string()))
+            .collect::<StdResult<Vec<String>>>()?,
+        spend_limit: state.spend_limit,
+    };
+
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
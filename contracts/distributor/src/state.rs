@@ -0,0 +1,25 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
+use cosmwasm_storage::{singleton, singleton_read};
+
+static KEY_CONFIG: &[u8] = b"config";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub gov_contract: CanonicalAddr,
+    pub anchor_token: CanonicalAddr,
+    /// contracts allowed to spend
+    pub whitelist: Vec<CanonicalAddr>,
+    /// maximum amount of each spend request
+    pub spend_limit: Uint128,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
@@ -0,0 +1,9 @@
+pub mod contract;
+pub mod state;
+
+mod staking;
+
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,194 @@
+use cosmwasm_std::{
+    attr, to_binary, Binary, CanonicalAddr, CosmosMsg, Deps, DepsMut, MessageInfo, Response,
+    StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use xdefi_token::gov::{PollStatus, StakerResponse};
+use xdefi_token::querier::query_token_balance;
+
+use crate::state::{
+    bank_read, bank_store, config_read, poll_read, poll_voter_store, state_read, state_store,
+    Config, Poll, State, TokenManager,
+};
+
+pub fn stake_voting_tokens(deps: DepsMut, sender: String, amount: Uint128) -> StdResult<Response> {
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Insufficient funds sent"));
+    }
+
+    let sender_address_raw = deps.api.addr_canonicalize(&sender)?;
+    let key = sender_address_raw.as_slice();
+
+    let mut token_manager = bank_read(deps.storage).may_load(key)?.unwrap_or_default();
+    let config: Config = config_read(deps.storage).load()?;
+    let mut state: State = state_read(deps.storage).load()?;
+
+    // balance already increased, so subtract deposit amount
+    let total_balance = load_token_balance(deps.as_ref(), &config, &state.contract_addr)?
+        .checked_sub(state.total_deposit + amount)?;
+
+    let share = if total_balance.is_zero() || state.total_share.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(state.total_share, total_balance)
+    };
+
+    token_manager.share += share;
+    state.total_share += share;
+
+    state_store(deps.storage).save(&state)?;
+    bank_store(deps.storage).save(key, &token_manager)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "staking"),
+        attr("sender", sender),
+        attr("share", share.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+// Withdraw amount if not staked. By default all funds will be withdrawn.
+pub fn withdraw_voting_tokens(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let key = sender_address_raw.as_slice();
+
+    let mut token_manager = match bank_read(deps.storage).may_load(key)? {
+        Some(token_manager) => token_manager,
+        None => return Err(StdError::generic_err("Nothing staked")),
+    };
+
+    if token_manager.share.is_zero() {
+        return Err(StdError::generic_err("Nothing staked"));
+    }
+
+    let config: Config = config_read(deps.storage).load()?;
+    let mut state: State = state_read(deps.storage).load()?;
+
+    // Load total share & total balance except proposal deposit amount
+    let total_share = state.total_share;
+    let total_balance = load_token_balance(deps.as_ref(), &config, &state.contract_addr)?
+        .checked_sub(state.total_deposit)?;
+
+    let locked_balance =
+        compute_locked_balance(deps.storage, &mut token_manager, &sender_address_raw)?;
+    let locked_share = locked_balance.multiply_ratio(total_share, total_balance);
+    let user_share = token_manager.share;
+
+    let withdraw_share = amount
+        .map(|v| {
+            std::cmp::max(
+                v.multiply_ratio(total_share, total_balance),
+                Uint128::new(1),
+            )
+        })
+        .unwrap_or_else(|| user_share.saturating_sub(locked_share));
+    let withdraw_amount =
+        amount.unwrap_or_else(|| withdraw_share.multiply_ratio(total_balance, total_share));
+
+    if locked_share + withdraw_share > user_share {
+        return Err(StdError::generic_err(
+            "User is trying to withdraw too many tokens.",
+        ));
+    }
+
+    let share = user_share.checked_sub(withdraw_share)?;
+    token_manager.share = share;
+
+    bank_store(deps.storage).save(key, &token_manager)?;
+
+    state.total_share = total_share.checked_sub(withdraw_share)?;
+    state_store(deps.storage).save(&state)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.anchor_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount: withdraw_amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            attr("action", "withdraw"),
+            attr("recipient", info.sender.as_str()),
+            attr("amount", withdraw_amount.to_string()),
+        ]))
+}
+
+// removes not in-progress poll voter info & unlock tokens
+// and returns the largest locked amount in participated polls.
+fn compute_locked_balance(
+    storage: &mut dyn Storage,
+    token_manager: &mut TokenManager,
+    voter: &CanonicalAddr,
+) -> StdResult<Uint128> {
+    let mut in_progress = vec![];
+    for (poll_id, voter_info) in token_manager.locked_balance.drain(..) {
+        let poll: Poll = poll_read(storage).load(&poll_id.to_be_bytes())?;
+        if poll.status == PollStatus::InProgress {
+            in_progress.push((poll_id, voter_info));
+        } else {
+            // remove voter info from the poll
+            poll_voter_store(storage, poll_id).remove(voter.as_slice());
+        }
+    }
+    token_manager.locked_balance = in_progress;
+
+    Ok(token_manager
+        .locked_balance
+        .iter()
+        .map(|(_, v)| v.balance)
+        .max()
+        .unwrap_or_default())
+}
+
+pub fn query_staker(deps: Deps, address: String) -> StdResult<Binary> {
+    let addr_raw = deps.api.addr_canonicalize(&address)?;
+    let config: Config = config_read(deps.storage).load()?;
+    let state: State = state_read(deps.storage).load()?;
+    let token_manager = bank_read(deps.storage)
+        .may_load(addr_raw.as_slice())?
+        .unwrap_or_default();
+
+    // filter out not in-progress polls
+    let mut locked_balance = vec![];
+    for (poll_id, voter_info) in token_manager.locked_balance {
+        let poll: Poll = poll_read(deps.storage).load(&poll_id.to_be_bytes())?;
+        if poll.status == PollStatus::InProgress {
+            locked_balance.push((poll_id, voter_info));
+        }
+    }
+
+    let total_balance = load_token_balance(deps, &config, &state.contract_addr)?
+        .checked_sub(state.total_deposit)?;
+
+    to_binary(&StakerResponse {
+        balance: if !state.total_share.is_zero() {
+            token_manager
+                .share
+                .multiply_ratio(total_balance, state.total_share)
+        } else {
+            Uint128::zero()
+        },
+        share: token_manager.share,
+        locked_balance,
+    })
+}
+
+/// voting token balance held by the gov contract, deposits included
+pub fn load_token_balance(
+    deps: Deps,
+    config: &Config,
+    contract_addr: &CanonicalAddr,
+) -> StdResult<Uint128> {
+    Ok(query_token_balance(
+        deps,
+        deps.api.addr_humanize(&config.anchor_token)?,
+        deps.api.addr_humanize(contract_addr)?,
+    )?
+    .into())
+}
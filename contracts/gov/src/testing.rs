@@ -0,0 +1,1109 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, ContractResult, CosmosMsg, Decimal, Env, OwnedDeps, Reply,
+    Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use xdefi_token::common::OrderBy;
+use xdefi_token::gov::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PollExecuteMsg, PollResponse,
+    PollStatus, PollsResponse, QueryMsg, StakerResponse, StateResponse, VoteOption, VoterInfo,
+    VotersResponse,
+};
+
+const VOTING_TOKEN: &str = "voting_token";
+const TEST_CREATOR: &str = "creator";
+const TEST_VOTER: &str = "voter1";
+const TEST_VOTER_2: &str = "voter2";
+const TEST_VOTER_3: &str = "voter3";
+const DEFAULT_QUORUM: u64 = 30u64;
+const DEFAULT_THRESHOLD: u64 = 50u64;
+const DEFAULT_VOTING_PERIOD: u64 = 10000u64;
+const DEFAULT_TIMELOCK_PERIOD: u64 = 10000u64;
+const DEFAULT_PROPOSAL_DEPOSIT: u128 = 10000000000u128;
+const DEFAULT_SNAPSHOT_PERIOD: u64 = 10u64;
+
+type MockDeps = OwnedDeps<MockStorage, MockApi, WasmMockQuerier>;
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        quorum: Decimal::percent(DEFAULT_QUORUM),
+        threshold: Decimal::percent(DEFAULT_THRESHOLD),
+        voting_period: DEFAULT_VOTING_PERIOD,
+        timelock_period: DEFAULT_TIMELOCK_PERIOD,
+        proposal_deposit: Uint128::new(DEFAULT_PROPOSAL_DEPOSIT),
+        snapshot_period: DEFAULT_SNAPSHOT_PERIOD,
+    }
+}
+
+fn mock_instantiate(deps: &mut MockDeps) {
+    let info = mock_info(TEST_CREATOR, &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), instantiate_msg())
+        .expect("contract successfully handles InstantiateMsg");
+
+    let msg = ExecuteMsg::RegisterContracts {
+        anchor_token: VOTING_TOKEN.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+fn mock_env_height(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+// the cw20 token has already moved the tokens when the hook is received
+fn set_gov_balance(deps: &mut MockDeps, amount: u128) {
+    deps.querier.with_token_balances(&[(
+        &VOTING_TOKEN.to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::new(amount))],
+    )]);
+}
+
+fn receive(
+    deps: &mut MockDeps,
+    env: Env,
+    sender: &str,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> StdResult<Response> {
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&msg).unwrap(),
+    });
+    execute(deps.as_mut(), env, mock_info(VOTING_TOKEN, &[]), msg)
+}
+
+fn create_poll_msg(execute_msgs: Option<Vec<PollExecuteMsg>>) -> Cw20HookMsg {
+    Cw20HookMsg::CreatePoll {
+        title: "test".to_string(),
+        description: "test".to_string(),
+        link: None,
+        execute_msgs,
+    }
+}
+
+fn query_poll(deps: &MockDeps, poll_id: u64) -> PollResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Poll { poll_id }).unwrap()).unwrap()
+}
+
+fn query_polls(deps: &MockDeps, filter: PollStatus) -> Vec<PollResponse> {
+    let res: PollsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Polls {
+                filter: Some(filter),
+                start_after: None,
+                limit: None,
+                order_by: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.polls
+}
+
+fn query_gov_state(deps: &MockDeps) -> StateResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, TEST_CREATOR.to_string());
+    assert_eq!(config.anchor_token, VOTING_TOKEN.to_string());
+    assert_eq!(config.quorum, Decimal::percent(DEFAULT_QUORUM));
+    assert_eq!(config.threshold, Decimal::percent(DEFAULT_THRESHOLD));
+    assert_eq!(config.voting_period, DEFAULT_VOTING_PERIOD);
+    assert_eq!(config.timelock_period, DEFAULT_TIMELOCK_PERIOD);
+    assert_eq!(
+        config.proposal_deposit,
+        Uint128::new(DEFAULT_PROPOSAL_DEPOSIT)
+    );
+    assert_eq!(config.snapshot_period, DEFAULT_SNAPSHOT_PERIOD);
+    assert!(config.trusted_forwarders.is_empty());
+
+    let state = query_gov_state(&deps);
+    assert_eq!(state.poll_count, 0);
+    assert_eq!(state.total_share, Uint128::zero());
+    assert_eq!(state.total_deposit, Uint128::zero());
+
+    // the voting token can only be registered once
+    let msg = ExecuteMsg::RegisterContracts {
+        anchor_token: "other_token".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // quorum and threshold are ratios
+    let mut msg = instantiate_msg();
+    msg.quorum = Decimal::percent(101);
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("quorum must be 0 to 1")));
+
+    let mut msg = instantiate_msg();
+    msg.threshold = Decimal::percent(101);
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("threshold must be 0 to 1")));
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("addr0001".to_string()),
+        quorum: None,
+        threshold: None,
+        voting_period: None,
+        timelock_period: None,
+        proposal_deposit: None,
+        snapshot_period: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        quorum: Some(Decimal::percent(120)),
+        threshold: None,
+        voting_period: None,
+        timelock_period: None,
+        proposal_deposit: None,
+        snapshot_period: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0001", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("quorum must be 0 to 1")));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        quorum: Some(Decimal::percent(20)),
+        threshold: Some(Decimal::percent(75)),
+        voting_period: Some(20000u64),
+        timelock_period: Some(20000u64),
+        proposal_deposit: Some(Uint128::new(123u128)),
+        snapshot_period: Some(60u64),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("addr0001", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, "addr0001".to_string());
+    assert_eq!(config.quorum, Decimal::percent(20));
+    assert_eq!(config.threshold, Decimal::percent(75));
+    assert_eq!(config.voting_period, 20000u64);
+    assert_eq!(config.timelock_period, 20000u64);
+    assert_eq!(config.proposal_deposit, Uint128::new(123u128));
+    assert_eq!(config.snapshot_period, 60u64);
+}
+
+#[test]
+fn create_poll() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    // only the voting token can send hooks
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: TEST_CREATOR.to_string(),
+        amount: Uint128::new(DEFAULT_PROPOSAL_DEPOSIT),
+        msg: to_binary(&create_poll_msg(None)).unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("other_token", &[]),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT - 1,
+        create_poll_msg(None),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(format!(
+            "Must deposit more than {} token",
+            DEFAULT_PROPOSAL_DEPOSIT
+        )))
+    );
+
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        Cw20HookMsg::CreatePoll {
+            title: "a".to_string(),
+            description: "test".to_string(),
+            link: None,
+            execute_msgs: None,
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("Title too short")));
+
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        Cw20HookMsg::CreatePoll {
+            title: "test".to_string(),
+            description: "test".to_string(),
+            link: Some("http://".to_string()),
+            execute_msgs: None,
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("Link too short")));
+
+    let env = mock_env();
+    let res = receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "create_poll"),
+            attr("creator", TEST_CREATOR),
+            attr("poll_id", "1"),
+            attr(
+                "end_height",
+                (env.block.height + DEFAULT_VOTING_PERIOD).to_string()
+            ),
+        ]
+    );
+
+    let poll = query_poll(&deps, 1);
+    assert_eq!(poll.creator, TEST_CREATOR.to_string());
+    assert_eq!(poll.status, PollStatus::InProgress);
+    assert_eq!(poll.end_height, env.block.height + DEFAULT_VOTING_PERIOD);
+    assert_eq!(poll.deposit_amount, Uint128::new(DEFAULT_PROPOSAL_DEPOSIT));
+
+    let state = query_gov_state(&deps);
+    assert_eq!(state.poll_count, 1);
+    assert_eq!(state.total_deposit, Uint128::new(DEFAULT_PROPOSAL_DEPOSIT));
+    assert_eq!(query_polls(&deps, PollStatus::InProgress).len(), 1);
+}
+
+#[test]
+fn stake_and_withdraw_voting_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    set_gov_balance(&mut deps, 11);
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        TEST_VOTER,
+        11,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "staking"),
+            attr("sender", TEST_VOTER),
+            attr("share", "11"),
+            attr("amount", "11"),
+        ]
+    );
+
+    // rewards sent to the gov contract grow the balance of every share
+    set_gov_balance(&mut deps, 22);
+    let staker: StakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Staker {
+                address: TEST_VOTER.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker.balance, Uint128::new(22));
+    assert_eq!(staker.share, Uint128::new(11));
+
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::new(24)),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "User is trying to withdraw too many tokens."
+        ))
+    );
+
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::new(10)),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_VOTER.to_string(),
+                amount: Uint128::new(10),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(query_gov_state(&deps).total_share, Uint128::new(6));
+
+    // withdraw everything left
+    set_gov_balance(&mut deps, 12);
+    let msg = ExecuteMsg::WithdrawVotingTokens { amount: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw"),
+            attr("recipient", TEST_VOTER),
+            attr("amount", "12"),
+        ]
+    );
+    assert_eq!(query_gov_state(&deps).total_share, Uint128::zero());
+
+    let msg = ExecuteMsg::WithdrawVotingTokens { amount: None };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER_2, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("Nothing staked")));
+}
+
+#[test]
+fn stake_voting_tokens_for() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    // only trusted forwarders stake on behalf of the voter
+    set_gov_balance(&mut deps, 11);
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        "staking0000",
+        11,
+        Cw20HookMsg::StakeVotingTokensFor {
+            staker: TEST_VOTER.to_string(),
+        },
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let msg = ExecuteMsg::UpdateTrustedForwarders {
+        add: Some(vec!["staking0000".to_string()]),
+        remove: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+    execute(deps.as_mut(), mock_env(), mock_info(TEST_CREATOR, &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.trusted_forwarders, vec!["staking0000".to_string()]);
+
+    // a contract forwarding the tokens stakes them for the voter
+    let res = receive(
+        &mut deps,
+        mock_env(),
+        "staking0000",
+        11,
+        Cw20HookMsg::StakeVotingTokensFor {
+            staker: TEST_VOTER.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "staking"),
+            attr("sender", TEST_VOTER),
+            attr("share", "11"),
+            attr("amount", "11"),
+        ]
+    );
+
+    let staker: StakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Staker {
+                address: TEST_VOTER.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker.share, Uint128::new(11));
+
+    let staker: StakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Staker {
+                address: "staking0000".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker.share, Uint128::zero());
+}
+
+#[test]
+fn cast_vote() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(10),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("Poll does not exist")));
+
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT);
+    receive(
+        &mut deps,
+        mock_env(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 10);
+    receive(
+        &mut deps,
+        mock_env(),
+        TEST_VOTER,
+        10,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(11),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "User does not have enough staked tokens."
+        ))
+    );
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(10),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_VOTER, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "cast_vote"),
+            attr("poll_id", "1"),
+            attr("amount", "10"),
+            attr("voter", TEST_VOTER),
+            attr("vote_option", "yes"),
+        ]
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("User has already voted.")));
+
+    // the voted amount is locked until the poll ends
+    let staker: StakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Staker {
+                address: TEST_VOTER.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        staker.locked_balance,
+        vec![(
+            1u64,
+            VoterInfo {
+                vote: VoteOption::Yes,
+                balance: Uint128::new(10),
+            }
+        )]
+    );
+
+    let msg = ExecuteMsg::WithdrawVotingTokens {
+        amount: Some(Uint128::new(1)),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "User is trying to withdraw too many tokens."
+        ))
+    );
+
+    let poll = query_poll(&deps, 1);
+    assert_eq!(poll.yes_votes, Uint128::new(10));
+    assert_eq!(poll.no_votes, Uint128::zero());
+
+    // voting is closed once the poll end height is passed
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::No,
+        amount: Uint128::new(1),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(poll.end_height + 1),
+        mock_info(TEST_VOTER_2, &[]),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("Poll is not in progress")));
+}
+
+#[test]
+fn end_poll_and_execute_messages_in_order() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let execute_msgs = vec![
+        PollExecuteMsg {
+            order: 2u64,
+            contract: "staking0000".to_string(),
+            msg: to_binary("second").unwrap(),
+        },
+        PollExecuteMsg {
+            order: 1u64,
+            contract: "staking0000".to_string(),
+            msg: to_binary("first").unwrap(),
+        },
+    ];
+
+    let env = mock_env();
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(Some(execute_msgs)),
+    )
+    .unwrap();
+
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 1000);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_VOTER,
+        1000,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(1000),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let end_height = env.block.height + DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height - 1),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Voting period has not expired"))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", ""),
+            attr("passed", "true"),
+        ]
+    );
+
+    // quorum was reached, the deposit is refunded
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: VOTING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: TEST_CREATOR.to_string(),
+                amount: Uint128::new(DEFAULT_PROPOSAL_DEPOSIT),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(query_gov_state(&deps).total_deposit, Uint128::zero());
+
+    let poll = query_poll(&deps, 1);
+    assert_eq!(poll.status, PollStatus::Passed);
+    assert_eq!(poll.total_balance_at_end_poll, Some(Uint128::new(1000)));
+
+    let msg = ExecuteMsg::ExecutePoll { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height + DEFAULT_TIMELOCK_PERIOD - 1),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Timelock period has not expired"))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height + DEFAULT_TIMELOCK_PERIOD),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&ExecuteMsg::ExecutePollMsgs { poll_id: 1 }).unwrap(),
+                funds: vec![],
+            }),
+            1
+        )]
+    );
+    assert_eq!(query_poll(&deps, 1).status, PollStatus::Executed);
+
+    // only the gov contract can dispatch the poll messages
+    let msg = ExecuteMsg::ExecutePollMsgs { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                msg: to_binary("first").unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                msg: to_binary("second").unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // a failing execution is recorded on the poll
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: ContractResult::Err("error".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(query_poll(&deps, 1).status, PollStatus::Failed);
+    assert_eq!(query_polls(&deps, PollStatus::Executed), vec![]);
+    assert_eq!(query_polls(&deps, PollStatus::Failed).len(), 1);
+
+    // the voter tokens are unlocked
+    let msg = ExecuteMsg::WithdrawVotingTokens { amount: None };
+    set_gov_balance(&mut deps, 1000);
+    let res = execute(deps.as_mut(), mock_env(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw"),
+            attr("recipient", TEST_VOTER),
+            attr("amount", "1000"),
+        ]
+    );
+}
+
+#[test]
+fn end_poll_rejected() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let env = mock_env();
+    set_gov_balance(&mut deps, 2 * DEFAULT_PROPOSAL_DEPOSIT);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+
+    set_gov_balance(&mut deps, 2 * DEFAULT_PROPOSAL_DEPOSIT + 1000);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_VOTER,
+        1000,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+
+    // poll 1 only gets 20% of the staked tokens
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(200),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    // poll 2 reaches the quorum with a majority of no
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 2,
+        vote: VoteOption::No,
+        amount: Uint128::new(1000),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(TEST_VOTER, &[]), msg).unwrap();
+
+    let end_env = mock_env_height(env.block.height + DEFAULT_VOTING_PERIOD);
+    let msg = ExecuteMsg::EndPoll { poll_id: 2 };
+    let res = execute(
+        deps.as_mut(),
+        end_env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "2"),
+            attr("rejected_reason", "Threshold not reached"),
+            attr("passed", "false"),
+        ]
+    );
+
+    // the quorum was reached, so the deposit is refunded
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        query_gov_state(&deps).total_deposit,
+        Uint128::new(DEFAULT_PROPOSAL_DEPOSIT)
+    );
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 1000);
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        end_env.clone(),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "end_poll"),
+            attr("poll_id", "1"),
+            attr("rejected_reason", "Quorum not reached"),
+            attr("passed", "false"),
+        ]
+    );
+
+    // the deposit is kept by the gov contract
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(query_gov_state(&deps).total_deposit, Uint128::zero());
+    assert_eq!(query_poll(&deps, 1).status, PollStatus::Rejected);
+
+    let res = execute(deps.as_mut(), end_env, mock_info(TEST_CREATOR, &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("Poll is not in progress")));
+    assert_eq!(query_polls(&deps, PollStatus::Rejected).len(), 2);
+
+    let msg = ExecuteMsg::ExecutePoll { poll_id: 2 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(env.block.height + DEFAULT_VOTING_PERIOD + DEFAULT_TIMELOCK_PERIOD),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Poll is not in passed status"))
+    );
+}
+
+#[test]
+fn snapshot_poll() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let env = mock_env();
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 1000);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_VOTER,
+        1000,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+
+    let end_height = env.block.height + DEFAULT_VOTING_PERIOD;
+    let msg = ExecuteMsg::SnapshotPoll { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height - DEFAULT_SNAPSHOT_PERIOD - 1),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Cannot snapshot at this height"))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height - DEFAULT_SNAPSHOT_PERIOD),
+        mock_info(TEST_CREATOR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "snapshot_poll"),
+            attr("poll_id", "1"),
+            attr("staked_amount", "1000"),
+        ]
+    );
+    assert_eq!(query_poll(&deps, 1).staked_amount, Some(Uint128::new(1000)));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height - 1),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Snapshot has already occurred"))
+    );
+
+    // a late staker can not dilute the quorum measured at the snapshot
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 11000);
+    receive(
+        &mut deps,
+        mock_env_height(end_height - 1),
+        TEST_VOTER_2,
+        10000,
+        Cw20HookMsg::StakeVotingTokens {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::CastVote {
+        poll_id: 1,
+        vote: VoteOption::Yes,
+        amount: Uint128::new(500),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env_height(end_height - 1),
+        mock_info(TEST_VOTER, &[]),
+        msg,
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::EndPoll { poll_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_height(end_height),
+        mock_info(TEST_CREATOR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("passed", "true"));
+    assert_eq!(
+        query_poll(&deps, 1).total_balance_at_end_poll,
+        Some(Uint128::new(1000))
+    );
+}
+
+#[test]
+fn query_voters_pagination() {
+    let mut deps = mock_dependencies(&[]);
+    mock_instantiate(&mut deps);
+
+    let env = mock_env();
+    set_gov_balance(&mut deps, DEFAULT_PROPOSAL_DEPOSIT + 3000);
+    receive(
+        &mut deps,
+        env.clone(),
+        TEST_CREATOR,
+        DEFAULT_PROPOSAL_DEPOSIT,
+        create_poll_msg(None),
+    )
+    .unwrap();
+
+    let voters = [TEST_VOTER, TEST_VOTER_2, TEST_VOTER_3];
+    for voter in voters.iter() {
+        receive(
+            &mut deps,
+            env.clone(),
+            voter,
+            1000,
+            Cw20HookMsg::StakeVotingTokens {},
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::CastVote {
+            poll_id: 1,
+            vote: VoteOption::Yes,
+            amount: Uint128::new(100),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(voter, &[]), msg).unwrap();
+    }
+
+    let query_voters = |start_after: Option<String>, order_by: OrderBy| -> VotersResponse {
+        from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Voters {
+                    poll_id: 1,
+                    start_after,
+                    limit: Some(2),
+                    order_by: Some(order_by),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    let page = query_voters(None, OrderBy::Asc);
+    assert_eq!(page.voters.len(), 2);
+    let last_page = query_voters(Some(page.voters[1].voter.clone()), OrderBy::Asc);
+    assert_eq!(last_page.voters.len(), 1);
+
+    let mut asc: Vec<String> = page
+        .voters
+        .into_iter()
+        .chain(last_page.voters)
+        .map(|item| item.voter)
+        .collect();
+
+    let page = query_voters(None, OrderBy::Desc);
+    assert_eq!(page.voters.len(), 2);
+    let last_page = query_voters(Some(page.voters[1].voter.clone()), OrderBy::Desc);
+    assert_eq!(last_page.voters.len(), 1);
+    assert_eq!(last_page.voters[0].vote, VoteOption::Yes);
+    assert_eq!(last_page.voters[0].balance, Uint128::new(100));
+
+    let desc: Vec<String> = page
+        .voters
+        .into_iter()
+        .chain(last_page.voters)
+        .map(|item| item.voter)
+        .collect();
+
+    asc.reverse();
+    assert_eq!(asc, desc);
+
+    asc.sort();
+    assert_eq!(
+        asc,
+        vec![
+            TEST_VOTER.to_string(),
+            TEST_VOTER_2.to_string(),
+            TEST_VOTER_3.to_string()
+        ]
+    );
+}
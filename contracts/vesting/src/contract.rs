@@ -0,0 +1,332 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use xdefi_token::common::OrderBy;
+use xdefi_token::vesting::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VestingAccount,
+    VestingAccountResponse, VestingAccountsResponse, VestingInfo,
+};
+
+use crate::state::{
+    read_config, read_vesting_info, read_vesting_infos, remove_vesting_info, store_config,
+    store_vesting_info, Config,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    store_config(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(&msg.owner)?,
+            anchor_token: deps.api.addr_canonicalize(&msg.anchor_token)?,
+            genesis_time: msg.genesis_time,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Claim {} => claim(deps, env, info),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            anchor_token,
+            genesis_time,
+        } => {
+            assert_owner_privilege(deps.as_ref(), &info)?;
+            update_config(deps, owner, anchor_token, genesis_time)
+        }
+        ExecuteMsg::RegisterVestingAccounts { vesting_accounts } => {
+            assert_owner_privilege(deps.as_ref(), &info)?;
+            register_vesting_accounts(deps, vesting_accounts)
+        }
+        ExecuteMsg::RevokeVestingAccount { address } => {
+            assert_owner_privilege(deps.as_ref(), &info)?;
+            revoke_vesting_account(deps, env, address)
+        }
+    }
+}
+
+fn assert_owner_privilege(deps: Deps, info: &MessageInfo) -> StdResult<()> {
+    if read_config(deps.storage)?.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    Ok(())
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    owner: Option<String>,
+    anchor_token: Option<String>,
+    genesis_time: Option<u64>,
+) -> StdResult<Response> {
+    let mut config = read_config(deps.storage)?;
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(&owner)?;
+    }
+
+    if let Some(anchor_token) = anchor_token {
+        config.anchor_token = deps.api.addr_canonicalize(&anchor_token)?;
+    }
+
+    if let Some(genesis_time) = genesis_time {
+        config.genesis_time = genesis_time;
+    }
+
+    store_config(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_vesting_schedules(vesting_schedules: &[(u64, u64, Uint128)]) -> StdResult<()> {
+    for vesting_schedule in vesting_schedules.iter() {
+        if vesting_schedule.0 >= vesting_schedule.1 {
+            return Err(StdError::generic_err(
+                "end_time must be bigger than start_time",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn register_vesting_accounts(
+    deps: DepsMut,
+    vesting_accounts: Vec<VestingAccount>,
+) -> StdResult<Response> {
+    let config = read_config(deps.storage)?;
+    for vesting_account in vesting_accounts.iter() {
+        assert_vesting_schedules(&vesting_account.schedules)?;
+    }
+
+    for vesting_account in vesting_accounts.into_iter() {
+        let vesting_address = deps.api.addr_canonicalize(&vesting_account.address)?;
+        if read_vesting_info(deps.storage, &vesting_address).is_ok() {
+            return Err(StdError::generic_err(
+                "vesting account is already registered",
+            ));
+        }
+
+        store_vesting_info(
+            deps.storage,
+            &vesting_address,
+            &VestingInfo {
+                last_claim_time: config.genesis_time,
+                schedules: vesting_account.schedules,
+                cliff: vesting_account.cliff,
+                revocable: vesting_account.revocable.unwrap_or(false),
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attribute("action", "register_vesting_accounts"))
+}
+
+pub fn revoke_vesting_account(deps: DepsMut, env: Env, address: String) -> StdResult<Response> {
+    let current_time = env.block.time.seconds();
+    let config = read_config(deps.storage)?;
+    let vesting_address = deps.api.addr_canonicalize(&address)?;
+    let vesting_info = read_vesting_info(deps.storage, &vesting_address)?;
+    if !vesting_info.revocable {
+        return Err(StdError::generic_err("Vesting account is not revocable"));
+    }
+
+    // what is vested so far goes to the account, the rest back to the owner
+    let claim_amount = compute_claim_amount(current_time, &vesting_info);
+    let unclaimed_amount = vesting_info
+        .schedules
+        .iter()
+        .map(|s| s.2)
+        .sum::<Uint128>()
+        .checked_sub(released_amount(&vesting_info, vesting_info.last_claim_time))?;
+    let refund_amount = unclaimed_amount.checked_sub(claim_amount)?;
+
+    remove_vesting_info(deps.storage, &vesting_address);
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !claim_amount.is_zero() {
+        messages.push(token_transfer_msg(
+            deps.as_ref(),
+            &config,
+            address.clone(),
+            claim_amount,
+        )?);
+    }
+
+    if !refund_amount.is_zero() {
+        let owner = deps.api.addr_humanize(&config.owner)?.to_string();
+        messages.push(token_transfer_msg(
+            deps.as_ref(),
+            &config,
+            owner,
+            refund_amount,
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "revoke_vesting_account"),
+        attr("address", address),
+        attr("claim_amount", claim_amount.to_string()),
+        attr("refund_amount", refund_amount.to_string()),
+    ]))
+}
+
+pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let current_time = env.block.time.seconds();
+    let address = info.sender;
+    let address_raw = deps.api.addr_canonicalize(address.as_str())?;
+
+    let config: Config = read_config(deps.storage)?;
+    let mut vesting_info: VestingInfo = read_vesting_info(deps.storage, &address_raw)?;
+
+    let claim_amount = compute_claim_amount(current_time, &vesting_info);
+    let messages: Vec<CosmosMsg> = if claim_amount.is_zero() {
+        vec![]
+    } else {
+        vec![token_transfer_msg(
+            deps.as_ref(),
+            &config,
+            address.to_string(),
+            claim_amount,
+        )?]
+    };
+
+    // the claim time only moves once the cliff is passed,
+    // so the amount vested before it is released at once
+    if current_time > vesting_info.last_claim_time && !is_before_cliff(current_time, &vesting_info)
+    {
+        vesting_info.last_claim_time = current_time;
+        store_vesting_info(deps.storage, &address_raw, &vesting_info)?;
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "claim"),
+        attr("address", address),
+        attr("claim_amount", claim_amount.to_string()),
+        attr("last_claim_time", vesting_info.last_claim_time.to_string()),
+    ]))
+}
+
+fn token_transfer_msg(
+    deps: Deps,
+    config: &Config,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: deps.api.addr_humanize(&config.anchor_token)?.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+    }))
+}
+
+fn is_before_cliff(current_time: u64, vesting_info: &VestingInfo) -> bool {
+    matches!(vesting_info.cliff, Some(cliff) if current_time < cliff)
+}
+
+fn compute_claim_amount(current_time: u64, vesting_info: &VestingInfo) -> Uint128 {
+    if is_before_cliff(current_time, vesting_info) {
+        return Uint128::zero();
+    }
+
+    released_amount(vesting_info, current_time)
+        .saturating_sub(released_amount(vesting_info, vesting_info.last_claim_time))
+}
+
+// amount released by the vesting schedules up to the given time
+fn released_amount(vesting_info: &VestingInfo, time: u64) -> Uint128 {
+    vesting_info
+        .schedules
+        .iter()
+        .map(|s| {
+            if time >= s.1 {
+                s.2
+            } else if time <= s.0 {
+                Uint128::zero()
+            } else {
+                s.2.multiply_ratio(time - s.0, s.1 - s.0)
+            }
+        })
+        .sum()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::VestingAccount { address } => to_binary(&query_vesting_account(deps, address)?),
+        QueryMsg::VestingAccounts {
+            start_after,
+            limit,
+            order_by,
+        } => to_binary(&query_vesting_accounts(deps, start_after, limit, order_by)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let state = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        owner: deps.api.addr_humanize(&state.owner)?.to_string(),
+        anchor_token: deps.api.addr_humanize(&state.anchor_token)?.to_string(),
+        genesis_time: state.genesis_time,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_vesting_account(deps: Deps, address: String) -> StdResult<VestingAccountResponse> {
+    let info = read_vesting_info(deps.storage, &deps.api.addr_canonicalize(&address)?)?;
+    let resp = VestingAccountResponse { address, info };
+
+    Ok(resp)
+}
+
+pub fn query_vesting_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<VestingAccountsResponse> {
+    let vesting_infos = if let Some(start_after) = start_after {
+        read_vesting_infos(
+            deps.storage,
+            Some(deps.api.addr_canonicalize(&start_after)?),
+            limit,
+            order_by,
+        )?
+    } else {
+        read_vesting_infos(deps.storage, None, limit, order_by)?
+    };
+
+    let vesting_account_responses: StdResult<Vec<VestingAccountResponse>> = vesting_infos
+        .iter()
+        .map(|vesting_account| {
+            Ok(VestingAccountResponse {
+                address: deps.api.addr_humanize(&vesting_account.0)?.to_string(),
+                info: vesting_account.1.clone(),
+            })
+        })
+        .collect();
+
+    Ok(VestingAccountsResponse {
+        vesting_accounts: vesting_account_responses?,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
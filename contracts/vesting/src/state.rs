@@ -0,0 +1,85 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
+use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use xdefi_token::common::OrderBy;
+use xdefi_token::vesting::VestingInfo;
+
+static KEY_CONFIG: &[u8] = b"config";
+static PREFIX_KEY_VESTING_INFO: &[u8] = b"vesting_info";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: CanonicalAddr,
+    pub anchor_token: CanonicalAddr,
+    /// vesting accounts start accruing from this time
+    pub genesis_time: u64,
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
+    singleton(storage, KEY_CONFIG).save(config)
+}
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+pub fn store_vesting_info(
+    storage: &mut dyn Storage,
+    vesting_address: &CanonicalAddr,
+    vesting_info: &VestingInfo,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_KEY_VESTING_INFO).save(vesting_address.as_slice(), vesting_info)
+}
+
+pub fn remove_vesting_info(storage: &mut dyn Storage, vesting_address: &CanonicalAddr) {
+    Bucket::<VestingInfo>::new(storage, PREFIX_KEY_VESTING_INFO).remove(vesting_address.as_slice())
+}
+
+pub fn read_vesting_info(
+    storage: &dyn Storage,
+    vesting_address: &CanonicalAddr,
+) -> StdResult<VestingInfo> {
+    ReadonlyBucket::new(storage, PREFIX_KEY_VESTING_INFO).load(vesting_address.as_slice())
+}
+
+pub fn read_vesting_infos(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+    order_by: Option<OrderBy>,
+) -> StdResult<Vec<(CanonicalAddr, VestingInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let (start, end, order_by) = match order_by {
+        Some(OrderBy::Asc) => (calc_range_start(start_after), None, OrderBy::Asc),
+        _ => (None, calc_range_end(start_after), OrderBy::Desc),
+    };
+
+    ReadonlyBucket::<VestingInfo>::new(storage, PREFIX_KEY_VESTING_INFO)
+        .range(start.as_deref(), end.as_deref(), order_by.into())
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
+}
+
+// this will set the first key after the provided key, by appending a 1 byte
+fn calc_range_start(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(1);
+        v
+    })
+}
+
+// this will set the first key after the provided key
+fn calc_range_end(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| addr.as_slice().to_vec())
+}
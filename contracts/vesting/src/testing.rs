@@ -0,0 +1,491 @@
+use crate::contract::{execute, instantiate, query};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, CosmosMsg, Env, StdError, SubMsg, Timestamp, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use xdefi_token::common::OrderBy;
+use xdefi_token::vesting::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, VestingAccount, VestingAccountResponse,
+    VestingAccountsResponse, VestingInfo,
+};
+
+fn mock_env_time(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(time);
+    env
+}
+
+fn transfer_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "xdefi_token".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: "owner".to_string(),
+        anchor_token: "xdefi_token".to_string(),
+        genesis_time: 100u64,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    assert_eq!(
+        from_binary::<ConfigResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()
+        )
+        .unwrap(),
+        ConfigResponse {
+            owner: "owner".to_string(),
+            anchor_token: "xdefi_token".to_string(),
+            genesis_time: 100u64,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("owner2".to_string()),
+        anchor_token: None,
+        genesis_time: None,
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("owner".to_string()),
+        anchor_token: None,
+        genesis_time: None,
+    };
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        anchor_token: Some("xdefi_token2".to_string()),
+        genesis_time: Some(1u64),
+    };
+    let info = mock_info("owner2", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<ConfigResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()
+        )
+        .unwrap(),
+        ConfigResponse {
+            owner: "owner2".to_string(),
+            anchor_token: "xdefi_token2".to_string(),
+            genesis_time: 1u64,
+        }
+    );
+}
+
+#[test]
+fn register_vesting_accounts() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![
+            VestingAccount {
+                address: "addr0000".to_string(),
+                schedules: vec![
+                    (100u64, 101u64, Uint128::from(100u128)),
+                    (100u64, 110u64, Uint128::from(100u128)),
+                    (100u64, 200u64, Uint128::from(100u128)),
+                ],
+                cliff: None,
+                revocable: None,
+            },
+            VestingAccount {
+                address: "addr0001".to_string(),
+                schedules: vec![(100u64, 110u64, Uint128::from(100u128))],
+                cliff: Some(105u64),
+                revocable: Some(true),
+            },
+            VestingAccount {
+                address: "addr0002".to_string(),
+                schedules: vec![(100u64, 200u64, Uint128::from(100u128))],
+                cliff: None,
+                revocable: None,
+            },
+        ],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    // schedules must end after they start
+    let invalid_msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![VestingAccount {
+            address: "addr0000".to_string(),
+            schedules: vec![(100u64, 100u64, Uint128::from(100u128))],
+            cliff: None,
+            revocable: None,
+        }],
+    };
+    let info = mock_info("owner", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), invalid_msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "end_time must be bigger than start_time"
+        ))
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // registering an account again would reset its claims
+    let msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![VestingAccount {
+            address: "addr0001".to_string(),
+            schedules: vec![(200u64, 210u64, Uint128::from(100u128))],
+            cliff: None,
+            revocable: None,
+        }],
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err(
+            "vesting account is already registered"
+        ))
+    );
+
+    assert_eq!(
+        from_binary::<VestingAccountResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::VestingAccount {
+                    address: "addr0001".to_string(),
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        VestingAccountResponse {
+            address: "addr0001".to_string(),
+            info: VestingInfo {
+                last_claim_time: 100u64,
+                schedules: vec![(100u64, 110u64, Uint128::from(100u128))],
+                cliff: Some(105u64),
+                revocable: true,
+            }
+        }
+    );
+
+    let res: VestingAccountsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VestingAccounts {
+                start_after: None,
+                limit: None,
+                order_by: Some(OrderBy::Asc),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.vesting_accounts.len(), 3);
+
+    let page: VestingAccountsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VestingAccounts {
+                start_after: Some(res.vesting_accounts[0].address.clone()),
+                limit: Some(1),
+                order_by: Some(OrderBy::Asc),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(page.vesting_accounts, vec![res.vesting_accounts[1].clone()]);
+
+    let page: VestingAccountsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VestingAccounts {
+                start_after: Some(res.vesting_accounts[2].address.clone()),
+                limit: None,
+                order_by: Some(OrderBy::Desc),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        page.vesting_accounts,
+        vec![
+            res.vesting_accounts[1].clone(),
+            res.vesting_accounts[0].clone()
+        ]
+    );
+}
+
+#[test]
+fn claim() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![VestingAccount {
+            address: "addr0000".to_string(),
+            schedules: vec![
+                (100u64, 101u64, Uint128::from(1000000u128)),
+                (100u64, 110u64, Uint128::from(100u128)),
+                (100u64, 200u64, Uint128::from(100u128)),
+            ],
+            cliff: None,
+            revocable: None,
+        }],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // nothing vests before the genesis time
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(50),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim"),
+            attr("address", "addr0000"),
+            attr("claim_amount", "0"),
+            attr("last_claim_time", "100"),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(101),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0000", 1000011u128)]);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "claim"),
+            attr("address", "addr0000"),
+            attr("claim_amount", "1000011"),
+            attr("last_claim_time", "101"),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(200),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0000", 189u128)]);
+
+    // everything is claimed
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(300),
+        info,
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+}
+
+#[test]
+fn claim_after_cliff() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![VestingAccount {
+            address: "addr0000".to_string(),
+            schedules: vec![(100u64, 200u64, Uint128::from(1000u128))],
+            cliff: Some(150u64),
+            revocable: None,
+        }],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(149),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(res.attributes[3], attr("last_claim_time", "100"));
+
+    // the amount vested before the cliff is released at once
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(150),
+        info.clone(),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0000", 500u128)]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(160),
+        info,
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("addr0000", 100u128)]);
+}
+
+#[test]
+fn revoke_vesting_account() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::RegisterVestingAccounts {
+        vesting_accounts: vec![
+            VestingAccount {
+                address: "addr0000".to_string(),
+                schedules: vec![(100u64, 200u64, Uint128::from(1000u128))],
+                cliff: None,
+                revocable: None,
+            },
+            VestingAccount {
+                address: "addr0001".to_string(),
+                schedules: vec![(100u64, 200u64, Uint128::from(1000u128))],
+                cliff: None,
+                revocable: Some(true),
+            },
+            VestingAccount {
+                address: "addr0002".to_string(),
+                schedules: vec![(100u64, 200u64, Uint128::from(1000u128))],
+                cliff: Some(150u64),
+                revocable: Some(true),
+            },
+        ],
+    };
+    let info = mock_info("owner", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RevokeVestingAccount {
+        address: "addr0001".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(120),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(120),
+        mock_info("owner", &[]),
+        ExecuteMsg::RevokeVestingAccount {
+            address: "addr0000".to_string(),
+        },
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("Vesting account is not revocable"))
+    );
+
+    // claim a part before the revocation
+    let _res = execute(
+        deps.as_mut(),
+        mock_env_time(120),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(150),
+        mock_info("owner", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_msg("addr0001", 300u128),
+            transfer_msg("owner", 500u128)
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "revoke_vesting_account"),
+            attr("address", "addr0001"),
+            attr("claim_amount", "300"),
+            attr("refund_amount", "500"),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(160),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Claim {},
+    );
+    assert!(res.is_err());
+
+    // nothing is vested for the account before its cliff
+    let res = execute(
+        deps.as_mut(),
+        mock_env_time(149),
+        mock_info("owner", &[]),
+        ExecuteMsg::RevokeVestingAccount {
+            address: "addr0002".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("owner", 1000u128)]);
+}
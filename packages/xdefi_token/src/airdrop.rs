@@ -7,6 +7,8 @@ use cosmwasm_std::Uint128;
 pub struct InstantiateMsg {
     pub owner: String,
     pub anchor_token: String,
+    /// staking contract bonding the tokens claimed with ClaimAndStake
+    pub staking_contract: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -14,6 +16,7 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     UpdateConfig {
         owner: Option<String>,
+        staking_contract: Option<String>,
     },
     RegisterMerkleRoot {
         merkle_root: String,
@@ -23,6 +26,14 @@ pub enum ExecuteMsg {
         amount: Uint128,
         proof: Vec<String>,
     },
+    /// Claim and bond the tokens in the staking contract for the claimer,
+    /// the staking contract has to trust this contract with `UpdateTrustedForwarders`
+    ClaimAndStake {
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+        pool_id: Option<u64>,
+    },
 }
 
 /// We currently take no arguments for migrations
@@ -43,6 +54,7 @@ pub enum QueryMsg {
 pub struct ConfigResponse {
     pub owner: String,
     pub anchor_token: String,
+    pub staking_contract: Option<String>,
 }
 
 // We define a custom struct for each query response
@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Api, CanonicalAddr, StdResult};
+
+/// AssetInfo contract_addr is usually passed from the cw20 hook
+/// so we can trust the contract_addr is properly validated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+impl AssetInfo {
+    pub fn to_raw(&self, api: &dyn Api) -> StdResult<AssetInfoRaw> {
+        match self {
+            AssetInfo::NativeToken { denom } => Ok(AssetInfoRaw::NativeToken {
+                denom: denom.to_string(),
+            }),
+            AssetInfo::Token { contract_addr } => Ok(AssetInfoRaw::Token {
+                contract_addr: api.addr_canonicalize(contract_addr)?,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfoRaw {
+    Token { contract_addr: CanonicalAddr },
+    NativeToken { denom: String },
+}
+
+impl AssetInfoRaw {
+    pub fn to_normal(&self, api: &dyn Api) -> StdResult<AssetInfo> {
+        match self {
+            AssetInfoRaw::NativeToken { denom } => Ok(AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            }),
+            AssetInfoRaw::Token { contract_addr } => Ok(AssetInfo::Token {
+                contract_addr: api.addr_humanize(contract_addr)?.to_string(),
+            }),
+        }
+    }
+}
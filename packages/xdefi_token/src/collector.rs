@@ -10,17 +10,22 @@ pub struct InstantiateMsg {
     pub anchor_token: String,
     pub distributor_contract: String,
     pub reward_factor: Decimal,
+    /// max spread of the sweep swaps, terraswap applies its default when none
+    pub max_spread: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     /// Update config interface
-    /// to enable reward_factor update
-    UpdateConfig { reward_factor: Option<Decimal> },
+    /// to enable reward_factor and max_spread update
+    UpdateConfig {
+        reward_factor: Option<Decimal>,
+        max_spread: Option<Decimal>,
+    },
     /// Public Message
     /// Sweep all given denom balance to ANC token
-    /// and execute Distribute message
+    /// and distribute the swapped tokens
     Sweep { denom: String },
 }
 
@@ -38,6 +43,7 @@ pub struct ConfigResponse {
     pub anchor_token: String,
     pub distributor_contract: String,
     pub reward_factor: Decimal,
+    pub max_spread: Option<Decimal>,
 }
 
 /// We currently take no arguments for migrations
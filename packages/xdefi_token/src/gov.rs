@@ -35,6 +35,11 @@ pub enum ExecuteMsg {
         proposal_deposit: Option<Uint128>,
         snapshot_period: Option<u64>,
     },
+    /// Owner operation to edit the contracts trusted to stake on behalf of a staker
+    UpdateTrustedForwarders {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
     CastVote {
         poll_id: u64,
         vote: VoteOption,
@@ -60,6 +65,9 @@ pub enum Cw20HookMsg {
     /// StakeVotingTokens a user can stake their mirror token to receive rewards
     /// or do vote on polls
     StakeVotingTokens {},
+    /// StakeVotingTokensFor stakes the tokens on behalf of the staker,
+    /// only honoured when sent by a trusted forwarder
+    StakeVotingTokensFor { staker: String },
     /// CreatePoll need to receive deposit from a proposer
     CreatePoll {
         title: String,
@@ -112,6 +120,7 @@ pub struct ConfigResponse {
     pub timelock_period: u64,
     pub proposal_deposit: Uint128,
     pub snapshot_period: u64,
+    pub trusted_forwarders: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
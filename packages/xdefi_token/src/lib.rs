@@ -1,4 +1,5 @@
 pub mod airdrop;
+pub mod asset;
 pub mod collector;
 pub mod common;
 pub mod community;
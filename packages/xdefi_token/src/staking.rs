@@ -1,25 +1,100 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, Decimal, Uint128};
+use cosmwasm_std::{
+    to_binary, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
+};
 use cw20::Cw20ReceiveMsg;
 
+use crate::asset::AssetInfo;
+use crate::vesting::VestingInfo;
+
+pub mod events;
+
+/// Pool created at instantiation, used when no pool_id is given
+pub const DEFAULT_POOL_ID: u64 = 0;
+
+/// Maximum number of stakers of a `StakerInfos` query
+pub const MAX_STAKER_INFOS: usize = 50;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub xdefi_token: String,
-    pub staking_token: String, // lp token of ANC-UST pair contract
+    pub staking_token: AssetInfo, // cw20 lp token or native denom to stake in the default pool
+    /// (start, end, amount) of each schedule, given ids in this order
     pub distribution_schedule: Vec<(u64, u64, Uint128)>,
 }
 
+/// Rewards emitted from the start block to the end block, along the emission curve
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardSchedule {
+    /// assigned in increasing order, never reused
+    pub id: u64,
+    pub start: u64,
+    pub end: u64,
+    pub amount: Uint128,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub curve: EmissionCurve,
+}
+
+/// Shape of the emission over a schedule, its amount is emitted in total whatever the shape
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmissionCurve {
+    /// The same amount every block
+    #[default]
+    Linear,
+    /// The emission per block is multiplied by the ratio every interval blocks,
+    /// e.g. a ratio of 0.5 halves it
+    Step { interval: u64, ratio: Decimal },
+    /// The emission per block is multiplied by the ratio every block
+    ExponentialDecay { ratio: Decimal },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
+    /// Bond the attached native coins, when the pool staking token is a native denom
+    BondNative {
+        pool_id: Option<u64>,
+        /// bond on behalf of this address,
+        /// only honoured when sent by a trusted forwarder or the staking contract
+        staker: Option<String>,
+    },
     Unbond {
+        pool_id: Option<u64>,
         amount: Uint128,
+        /// Also withdraw pending rewards in the same transaction
+        claim_rewards: Option<bool>,
+    },
+    /// Unbond and send the staking tokens to a contract with the given hook
+    /// message (cw20 `Send`, or an execute with attached funds for native coins),
+    /// only `Bond` and `BondNative` are accepted and they get to credit the staker
+    UnbondTo {
+        pool_id: Option<u64>,
+        amount: Uint128,
+        contract: String,
+        msg: Binary,
     },
     /// Withdraw pending rewards
-    Withdraw {},
+    Withdraw {
+        pool_id: Option<u64>,
+    },
+    /// Withdraw pending rewards and send them to a contract with the given
+    /// hook message (cw20 `Send`), only `Bond` and the gov `StakeVotingTokens`
+    /// are accepted and they get to credit the staker
+    WithdrawTo {
+        pool_id: Option<u64>,
+        contract: String,
+        msg: Binary,
+    },
+    /// Unbond the whole bond amount, withdraw pending rewards
+    /// and remove the staker info
+    Exit {
+        pool_id: Option<u64>,
+    },
     /// Owner operation to stop distribution on current staking contract
     /// and send remaining tokens to the new contract
     MigrateStaking {
@@ -29,20 +104,144 @@ pub enum ExecuteMsg {
     ChangeOwner {
         new_owner_address: String,
     },
+    /// Owner operation to add a (start, end, amount) schedule,
+    /// its id is returned in the response data as `AddRewardResponse`
     AddReward {
         reward_schedule: (u64, u64, Uint128),
+        label: Option<String>,
+        /// Linear when not given
+        curve: Option<EmissionCurve>,
     },
-
+    /// Owner operation to change the amount of a schedule not started yet
     ModifyReward {
         new_emission: Uint128,
-        index_schedule: usize,
+        schedule_id: u64,
+    },
+    /// Owner operation to set the length of the rolling reward periods,
+    /// only once the current period finished
+    SetRewardsDuration {
+        rewards_duration: u64,
+    },
+
+    /// Owner operation to add a pool sharing the distribution schedule
+    /// by its allocation points
+    AddPool {
+        staking_token: AssetInfo,
+        alloc_point: u64,
+    },
+    /// Owner operation to re-weight a pool
+    SetPool {
+        pool_id: u64,
+        alloc_point: u64,
+    },
+    /// Owner operation to set the bond caps of a pool, unset caps are not enforced
+    UpdateBondLimits {
+        pool_id: Option<u64>,
+        max_total_bond: Option<Uint128>,
+        max_bond_per_staker: Option<Uint128>,
+        min_bond_amount: Option<Uint128>,
+    },
+    /// Owner operation to toggle allowlist mode and edit the allowlist;
+    /// while enabled only allowlisted addresses can bond
+    UpdateAllowlist {
+        enabled: Option<bool>,
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
+    /// Owner operation to lock withdrawn rewards in a vesting ledger,
+    /// unlocking linearly over the given number of blocks; None pays out instantly
+    SetRewardVesting {
+        period: Option<u64>,
+    },
+    /// Owner operation to pay rewards out of the distributor contract
+    /// with `Spend`, instead of the staking contract balance; None disables it
+    SetDistributor {
+        distributor_contract: Option<String>,
+    },
+    /// Owner operation to edit the contracts trusted to bond on behalf of a staker,
+    /// e.g. the airdrop or other staking contracts forwarding withdrawn tokens
+    UpdateTrustedForwarders {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
+    /// Claim the vested part of the rewards locked by withdrawals
+    ClaimVested {},
+    /// Owner operation to register a contract notified of every bond change
+    AddHook {
+        addr: String,
+    },
+    /// Owner operation to unregister a hook contract
+    RemoveHook {
+        addr: String,
+    },
+    /// Owner operation to block withdrawals and unbonding of a staker
+    Freeze {
+        staker: String,
+    },
+    /// Owner operation to lift a freeze
+    Unfreeze {
+        staker: String,
+    },
+    /// Distribute the attached native coins to the pool stakers at once,
+    /// paid out along with the rewards on withdrawal
+    DistributeNativeNow {
+        pool_id: Option<u64>,
+    },
+    /// Owner operation to restart the schedule emission halted by an insolvency alert,
+    /// once the reward balance covers the rewards owed; the rewards scheduled
+    /// while halted are emitted at once
+    ResumeEmission {},
+    /// Move the next `limit` stakers of a contract migrated from the single pool layout
+    /// to the default pool, counting the rewards owed to them as distributed;
+    /// the stakers not migrated yet are read from the single pool until then
+    MigrateStakers {
+        limit: Option<u32>,
     },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    Bond {},
+    Bond {
+        pool_id: Option<u64>,
+        /// bond on behalf of this address instead of the cw20 sender,
+        /// only honoured when sent by a trusted forwarder or the staking contract
+        staker: Option<String>,
+    },
+    /// Distribute the sent xdefi tokens to the pool stakers at once,
+    /// instead of streaming them over a distribution schedule;
+    /// they are forwarded to the distributor when it pays the rewards
+    DistributeNow { pool_id: Option<u64> },
+    /// Owner operation to emit the sent xdefi tokens over `rewards_duration` blocks
+    /// from now, the leftover of the current rolling period is added to the new one;
+    /// the new schedule id is returned in the response data as `AddRewardResponse`
+    NotifyRewardAmount {},
+}
+
+/// Message dispatched to every registered hook contract when a staker bond changes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeChangedHookMsg {
+    StakeChangedHook {
+        pool_id: u64,
+        addr: String,
+        old_bond: Uint128,
+        new_bond: Uint128,
+    },
+    /// The rewards moved to the new staking contract, the bonds stay until the
+    /// stakers unbond, each of them dispatching its own `StakeChangedHook`
+    StakingMigrated { new_staking_contract: String },
+}
+
+impl StakeChangedHookMsg {
+    /// serializes the message into a wasm execute of the given hook contract
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_binary(&self)?,
+            funds: vec![],
+        }))
+    }
 }
 
 /// We currently take no arguments for migrations
@@ -56,34 +255,227 @@ pub enum QueryMsg {
     State {
         block_height: Option<u64>,
     },
+    Pool {
+        pool_id: u64,
+        block_height: Option<u64>,
+    },
+    Pools {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     StakerInfo {
+        staker: String,
+        pool_id: Option<u64>,
+        block_height: Option<u64>,
+    },
+    /// `StakerInfo` of several stakers of a pool at once, at most `MAX_STAKER_INFOS`
+    StakerInfos {
+        stakers: Vec<String>,
+        pool_id: Option<u64>,
+        block_height: Option<u64>,
+    },
+    /// Stakers of a pool sorted by bond amount, largest first;
+    /// start_after is the `next_start_after` cursor of the previous page
+    TopStakers {
+        pool_id: Option<u64>,
+        start_after: Option<(Uint128, String)>,
+        limit: Option<u32>,
+    },
+    /// Bond caps of a pool and how much can still be bonded,
+    /// in total and by the given staker
+    Capacity {
+        pool_id: Option<u64>,
+        staker: Option<String>,
+    },
+    Hooks {},
+    RewardVesting {
         staker: String,
         block_height: Option<u64>,
     },
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    FrozenStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Balances of the staking and reward tokens against what the contract owes
+    Solvency {},
+    /// End of the current rolling reward period
+    PeriodFinish {},
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub xdefi_token: String,
+    /// staking token of the default pool, its cw20 address or native denom
     pub staking_token: String,
-    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    /// schedules not fully distributed yet, the finished ones are archived
+    pub distribution_schedule: Vec<RewardSchedule>,
+    pub reward_vesting_period: Option<u64>,
+    pub distributor_contract: Option<String>,
+    pub trusted_forwarders: Vec<String>,
+}
+
+/// Response data of `AddReward`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AddRewardResponse {
+    pub schedule_id: u64,
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StateResponse {
     pub last_distributed: u64,
+    /// bonded in the default pool
     pub total_bond_amount: Uint128,
+    /// reward index of the default pool
     pub global_reward_index: Decimal,
+    pub total_alloc_point: u64,
+    pub reward_per_alloc_point: Decimal,
+    pub pool_count: u64,
     pub owner_address: CanonicalAddr,
+    /// rewards emitted by the schedules and distributed at once, over the contract lifetime
+    pub total_distributed: Uint128,
+    /// rewards withdrawn by all stakers, over the contract lifetime
+    pub total_claimed: Uint128,
+    /// withdrawn rewards still locked in the vesting ledger
+    pub total_vesting: Uint128,
+    /// set when the rewards owed exceeded the reward balance, nothing is emitted until resumed
+    pub emission_halted: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    pub staking_tokens: Vec<StakingTokenSolvency>,
+    /// reward tokens held by the payer, the distributor when set,
+    /// excluding the reward tokens bonded in the pools
+    pub reward_balance: Uint128,
+    /// rewards emitted or distributed and not paid out yet, including the vesting ones
+    /// and the ones emitted to pools which did not credit them yet
+    pub pending_rewards: Uint128,
+    /// rewards the schedules are still to emit, including the ones scheduled while halted
+    pub unemitted_rewards: Uint128,
+    pub reward_liability: Uint128,
+    pub reward_shortfall: Uint128,
+    pub emission_halted: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PeriodFinishResponse {
+    /// zero until the first `NotifyRewardAmount`
+    pub period_finish: u64,
+    pub rewards_duration: u64,
+    /// rewards emitted per block by the rolling period, zero once finished
+    pub reward_rate: Decimal,
+}
+
+/// Balance of a staking token against the amount bonded over all its pools
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingTokenSolvency {
+    pub staking_token: AssetInfo,
+    pub balance: Uint128,
+    pub liability: Uint128,
+    pub shortfall: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub pool_id: u64,
+    pub staking_token: AssetInfo,
+    pub alloc_point: u64,
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
 }
 
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerInfoResponse {
     pub staker: String,
+    pub pool_id: u64,
     pub reward_index: Decimal,
     pub bond_amount: Uint128,
     pub pending_reward: Uint128,
+    /// native coins distributed with `DistributeNativeNow`, not withdrawn yet
+    pub pending_native_rewards: Vec<Coin>,
+    /// rewards withdrawn by the staker from the pool, over the contract lifetime
+    pub total_claimed: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfosResponse {
+    pub staker_infos: Vec<StakerInfoResponse>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TopStakersResponse {
+    pub stakers: Vec<TopStakerResponse>,
+    /// bond amount and address of the last staker of the page, none when it is empty
+    pub next_start_after: Option<(Uint128, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TopStakerResponse {
+    pub staker: String,
+    pub bond_amount: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CapacityResponse {
+    pub pool_id: u64,
+    pub max_total_bond: Option<Uint128>,
+    pub max_bond_per_staker: Option<Uint128>,
+    pub min_bond_amount: Option<Uint128>,
+    pub total_bond_amount: Uint128,
+    /// None when the pool is uncapped
+    pub remaining_capacity: Option<Uint128>,
+    /// total_bond_amount / max_total_bond, None when the pool is uncapped
+    pub filled_ratio: Option<Decimal>,
+    /// None when no staker is given or no cap applies to them
+    pub staker_remaining_capacity: Option<Uint128>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistResponse {
+    pub enabled: bool,
+    pub addresses: Vec<String>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FrozenStakersResponse {
+    pub stakers: Vec<String>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardVestingResponse {
+    pub staker: String,
+    /// vested rewards not claimed yet
+    pub vested: Uint128,
+    pub unvested: Uint128,
+    /// vesting schedules in block heights, last_claim_time is a block height as well
+    pub info: VestingInfo,
 }
@@ -0,0 +1,905 @@
+//! Typed events emitted by the staking contract.
+//!
+//! Every execute path adds one of these events to its `Response`. On chain the
+//! event type is prefixed by `wasm-`, e.g. `wasm-xdefi_staking_bond`; the parser
+//! accepts event types with or without the prefix.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, Event, StdError, StdResult, Uint128};
+
+pub const EVENT_BOND: &str = "xdefi_staking_bond";
+pub const EVENT_UNBOND: &str = "xdefi_staking_unbond";
+pub const EVENT_WITHDRAW: &str = "xdefi_staking_withdraw";
+pub const EVENT_EXIT: &str = "xdefi_staking_exit";
+pub const EVENT_CHANGE_OWNER: &str = "xdefi_staking_change_owner";
+pub const EVENT_ADD_REWARD_SCHEDULE: &str = "xdefi_staking_add_reward_schedule";
+pub const EVENT_MODIFY_REWARD_SCHEDULE: &str = "xdefi_staking_modify_reward_schedule";
+pub const EVENT_MIGRATE_STAKING: &str = "xdefi_staking_migrate_staking";
+pub const EVENT_ADD_POOL: &str = "xdefi_staking_add_pool";
+pub const EVENT_SET_POOL: &str = "xdefi_staking_set_pool";
+pub const EVENT_UPDATE_BOND_LIMITS: &str = "xdefi_staking_update_bond_limits";
+pub const EVENT_UPDATE_ALLOWLIST: &str = "xdefi_staking_update_allowlist";
+pub const EVENT_SET_REWARD_VESTING: &str = "xdefi_staking_set_reward_vesting";
+pub const EVENT_SET_DISTRIBUTOR: &str = "xdefi_staking_set_distributor";
+pub const EVENT_UPDATE_TRUSTED_FORWARDERS: &str = "xdefi_staking_update_trusted_forwarders";
+pub const EVENT_VEST_REWARDS: &str = "xdefi_staking_vest_rewards";
+pub const EVENT_CLAIM_VESTED: &str = "xdefi_staking_claim_vested";
+pub const EVENT_ADD_HOOK: &str = "xdefi_staking_add_hook";
+pub const EVENT_REMOVE_HOOK: &str = "xdefi_staking_remove_hook";
+pub const EVENT_FREEZE: &str = "xdefi_staking_freeze";
+pub const EVENT_UNFREEZE: &str = "xdefi_staking_unfreeze";
+pub const EVENT_DISTRIBUTE_NOW: &str = "xdefi_staking_distribute_now";
+pub const EVENT_INSOLVENCY_ALERT: &str = "xdefi_staking_insolvency_alert";
+pub const EVENT_RESUME_EMISSION: &str = "xdefi_staking_resume_emission";
+pub const EVENT_NOTIFY_REWARD_AMOUNT: &str = "xdefi_staking_notify_reward_amount";
+pub const EVENT_SET_REWARDS_DURATION: &str = "xdefi_staking_set_rewards_duration";
+
+/// prefix the chain adds to the type of events emitted by contracts
+pub const WASM_EVENT_PREFIX: &str = "wasm-";
+
+/// An event with a fixed type, convertible to and from a cosmwasm `Event`
+pub trait TypedEvent: Sized {
+    const NAME: &'static str;
+
+    fn to_event(&self) -> Event;
+
+    fn from_event(event: &Event) -> StdResult<Self>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BondEvent {
+    pub pool_id: u64,
+    pub staker: String,
+    pub amount: Uint128,
+    /// staker bond after the operation
+    pub bond_amount: Uint128,
+    /// pool bond after the operation
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+}
+
+impl TypedEvent for BondEvent {
+    const NAME: &'static str = EVENT_BOND;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("bond_amount", self.bond_amount.to_string())
+            .add_attribute("total_bond_amount", self.total_bond_amount.to_string())
+            .add_attribute("global_reward_index", self.global_reward_index.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(BondEvent {
+            pool_id: required(event, "pool_id")?,
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+            bond_amount: required(event, "bond_amount")?,
+            total_bond_amount: required(event, "total_bond_amount")?,
+            global_reward_index: required(event, "global_reward_index")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnbondEvent {
+    pub pool_id: u64,
+    pub staker: String,
+    pub amount: Uint128,
+    /// rewards claimed along with the unbonded tokens
+    pub reward_amount: Uint128,
+    /// staker bond after the operation
+    pub bond_amount: Uint128,
+    /// pool bond after the operation
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+    /// contract the tokens were sent to, for UnbondTo
+    pub contract: Option<String>,
+}
+
+impl TypedEvent for UnbondEvent {
+    const NAME: &'static str = EVENT_UNBOND;
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("reward_amount", self.reward_amount.to_string())
+            .add_attribute("bond_amount", self.bond_amount.to_string())
+            .add_attribute("total_bond_amount", self.total_bond_amount.to_string())
+            .add_attribute("global_reward_index", self.global_reward_index.to_string());
+        with_optional(event, "contract", &self.contract)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(UnbondEvent {
+            pool_id: required(event, "pool_id")?,
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+            reward_amount: required(event, "reward_amount")?,
+            bond_amount: required(event, "bond_amount")?,
+            total_bond_amount: required(event, "total_bond_amount")?,
+            global_reward_index: required(event, "global_reward_index")?,
+            contract: optional(event, "contract")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawEvent {
+    pub pool_id: u64,
+    pub staker: String,
+    pub amount: Uint128,
+    pub global_reward_index: Decimal,
+    /// contract the rewards were sent to, for WithdrawTo
+    pub contract: Option<String>,
+}
+
+impl TypedEvent for WithdrawEvent {
+    const NAME: &'static str = EVENT_WITHDRAW;
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("global_reward_index", self.global_reward_index.to_string());
+        with_optional(event, "contract", &self.contract)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(WithdrawEvent {
+            pool_id: required(event, "pool_id")?,
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+            global_reward_index: required(event, "global_reward_index")?,
+            contract: optional(event, "contract")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExitEvent {
+    pub pool_id: u64,
+    pub staker: String,
+    pub amount: Uint128,
+    pub reward_amount: Uint128,
+    /// pool bond after the operation
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+}
+
+impl TypedEvent for ExitEvent {
+    const NAME: &'static str = EVENT_EXIT;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("reward_amount", self.reward_amount.to_string())
+            .add_attribute("total_bond_amount", self.total_bond_amount.to_string())
+            .add_attribute("global_reward_index", self.global_reward_index.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ExitEvent {
+            pool_id: required(event, "pool_id")?,
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+            reward_amount: required(event, "reward_amount")?,
+            total_bond_amount: required(event, "total_bond_amount")?,
+            global_reward_index: required(event, "global_reward_index")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeOwnerEvent {
+    pub new_owner: String,
+}
+
+impl TypedEvent for ChangeOwnerEvent {
+    const NAME: &'static str = EVENT_CHANGE_OWNER;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("new_owner", &self.new_owner)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ChangeOwnerEvent {
+            new_owner: required(event, "new_owner")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddRewardScheduleEvent {
+    pub schedule_id: u64,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub amount: Uint128,
+    pub label: Option<String>,
+}
+
+impl TypedEvent for AddRewardScheduleEvent {
+    const NAME: &'static str = EVENT_ADD_REWARD_SCHEDULE;
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::NAME)
+            .add_attribute("schedule_id", self.schedule_id.to_string())
+            .add_attribute("start_block", self.start_block.to_string())
+            .add_attribute("end_block", self.end_block.to_string())
+            .add_attribute("amount", self.amount.to_string());
+        with_optional(event, "label", &self.label)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(AddRewardScheduleEvent {
+            schedule_id: required(event, "schedule_id")?,
+            start_block: required(event, "start_block")?,
+            end_block: required(event, "end_block")?,
+            amount: required(event, "amount")?,
+            label: optional(event, "label")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModifyRewardScheduleEvent {
+    pub schedule_id: u64,
+    pub start_block: u64,
+    pub end_block: u64,
+    pub amount: Uint128,
+}
+
+impl TypedEvent for ModifyRewardScheduleEvent {
+    const NAME: &'static str = EVENT_MODIFY_REWARD_SCHEDULE;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("schedule_id", self.schedule_id.to_string())
+            .add_attribute("start_block", self.start_block.to_string())
+            .add_attribute("end_block", self.end_block.to_string())
+            .add_attribute("amount", self.amount.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ModifyRewardScheduleEvent {
+            schedule_id: required(event, "schedule_id")?,
+            start_block: required(event, "start_block")?,
+            end_block: required(event, "end_block")?,
+            amount: required(event, "amount")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MigrateStakingEvent {
+    pub new_staking_contract: String,
+    pub distributed_amount: Uint128,
+    pub remaining_amount: Uint128,
+}
+
+impl TypedEvent for MigrateStakingEvent {
+    const NAME: &'static str = EVENT_MIGRATE_STAKING;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("new_staking_contract", &self.new_staking_contract)
+            .add_attribute("distributed_amount", self.distributed_amount.to_string())
+            .add_attribute("remaining_amount", self.remaining_amount.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(MigrateStakingEvent {
+            new_staking_contract: required(event, "new_staking_contract")?,
+            distributed_amount: required(event, "distributed_amount")?,
+            remaining_amount: required(event, "remaining_amount")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddPoolEvent {
+    pub pool_id: u64,
+    pub alloc_point: u64,
+    /// sum of all pool allocation points after the operation
+    pub total_alloc_point: u64,
+}
+
+impl TypedEvent for AddPoolEvent {
+    const NAME: &'static str = EVENT_ADD_POOL;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("alloc_point", self.alloc_point.to_string())
+            .add_attribute("total_alloc_point", self.total_alloc_point.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(AddPoolEvent {
+            pool_id: required(event, "pool_id")?,
+            alloc_point: required(event, "alloc_point")?,
+            total_alloc_point: required(event, "total_alloc_point")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetPoolEvent {
+    pub pool_id: u64,
+    pub alloc_point: u64,
+    /// sum of all pool allocation points after the operation
+    pub total_alloc_point: u64,
+}
+
+impl TypedEvent for SetPoolEvent {
+    const NAME: &'static str = EVENT_SET_POOL;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("alloc_point", self.alloc_point.to_string())
+            .add_attribute("total_alloc_point", self.total_alloc_point.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(SetPoolEvent {
+            pool_id: required(event, "pool_id")?,
+            alloc_point: required(event, "alloc_point")?,
+            total_alloc_point: required(event, "total_alloc_point")?,
+        })
+    }
+}
+
+/// unset limits are omitted from the event attributes
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateBondLimitsEvent {
+    pub pool_id: u64,
+    pub max_total_bond: Option<Uint128>,
+    pub max_bond_per_staker: Option<Uint128>,
+    pub min_bond_amount: Option<Uint128>,
+}
+
+impl TypedEvent for UpdateBondLimitsEvent {
+    const NAME: &'static str = EVENT_UPDATE_BOND_LIMITS;
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::NAME).add_attribute("pool_id", self.pool_id.to_string());
+        let event = with_optional(event, "max_total_bond", &self.max_total_bond);
+        let event = with_optional(event, "max_bond_per_staker", &self.max_bond_per_staker);
+        with_optional(event, "min_bond_amount", &self.min_bond_amount)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(UpdateBondLimitsEvent {
+            pool_id: required(event, "pool_id")?,
+            max_total_bond: optional(event, "max_total_bond")?,
+            max_bond_per_staker: optional(event, "max_bond_per_staker")?,
+            min_bond_amount: optional(event, "min_bond_amount")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateAllowlistEvent {
+    pub enabled: bool,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl TypedEvent for UpdateAllowlistEvent {
+    const NAME: &'static str = EVENT_UPDATE_ALLOWLIST;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("enabled", self.enabled.to_string())
+            .add_attribute("added", self.added.join(","))
+            .add_attribute("removed", self.removed.join(","))
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(UpdateAllowlistEvent {
+            enabled: required(event, "enabled")?,
+            added: list(event, "added")?,
+            removed: list(event, "removed")?,
+        })
+    }
+}
+
+/// a period of None disables reward vesting
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetRewardVestingEvent {
+    pub period: Option<u64>,
+}
+
+impl TypedEvent for SetRewardVestingEvent {
+    const NAME: &'static str = EVENT_SET_REWARD_VESTING;
+
+    fn to_event(&self) -> Event {
+        with_optional(Event::new(Self::NAME), "period", &self.period)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(SetRewardVestingEvent {
+            period: optional(event, "period")?,
+        })
+    }
+}
+
+/// a distributor_contract of None pays rewards out of the staking contract balance
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetDistributorEvent {
+    pub distributor_contract: Option<String>,
+}
+
+impl TypedEvent for SetDistributorEvent {
+    const NAME: &'static str = EVENT_SET_DISTRIBUTOR;
+
+    fn to_event(&self) -> Event {
+        with_optional(
+            Event::new(Self::NAME),
+            "distributor_contract",
+            &self.distributor_contract,
+        )
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(SetDistributorEvent {
+            distributor_contract: optional(event, "distributor_contract")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateTrustedForwardersEvent {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl TypedEvent for UpdateTrustedForwardersEvent {
+    const NAME: &'static str = EVENT_UPDATE_TRUSTED_FORWARDERS;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("added", self.added.join(","))
+            .add_attribute("removed", self.removed.join(","))
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(UpdateTrustedForwardersEvent {
+            added: list(event, "added")?,
+            removed: list(event, "removed")?,
+        })
+    }
+}
+
+/// emitted along with withdrawals when the rewards are locked in the vesting ledger
+#[derive(Clone, Debug, PartialEq)]
+pub struct VestRewardsEvent {
+    pub staker: String,
+    pub amount: Uint128,
+    pub start_block: u64,
+    pub end_block: u64,
+}
+
+impl TypedEvent for VestRewardsEvent {
+    const NAME: &'static str = EVENT_VEST_REWARDS;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("start_block", self.start_block.to_string())
+            .add_attribute("end_block", self.end_block.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(VestRewardsEvent {
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+            start_block: required(event, "start_block")?,
+            end_block: required(event, "end_block")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimVestedEvent {
+    pub staker: String,
+    pub amount: Uint128,
+}
+
+impl TypedEvent for ClaimVestedEvent {
+    const NAME: &'static str = EVENT_CLAIM_VESTED;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("staker", &self.staker)
+            .add_attribute("amount", self.amount.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ClaimVestedEvent {
+            staker: required(event, "staker")?,
+            amount: required(event, "amount")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddHookEvent {
+    pub addr: String,
+}
+
+impl TypedEvent for AddHookEvent {
+    const NAME: &'static str = EVENT_ADD_HOOK;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("addr", &self.addr)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(AddHookEvent {
+            addr: required(event, "addr")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoveHookEvent {
+    pub addr: String,
+}
+
+impl TypedEvent for RemoveHookEvent {
+    const NAME: &'static str = EVENT_REMOVE_HOOK;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("addr", &self.addr)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(RemoveHookEvent {
+            addr: required(event, "addr")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FreezeEvent {
+    pub staker: String,
+}
+
+impl TypedEvent for FreezeEvent {
+    const NAME: &'static str = EVENT_FREEZE;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("staker", &self.staker)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(FreezeEvent {
+            staker: required(event, "staker")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnfreezeEvent {
+    pub staker: String,
+}
+
+impl TypedEvent for UnfreezeEvent {
+    const NAME: &'static str = EVENT_UNFREEZE;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("staker", &self.staker)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(UnfreezeEvent {
+            staker: required(event, "staker")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistributeNowEvent {
+    pub pool_id: u64,
+    /// xdefi token address, or the native denom
+    pub asset: String,
+    pub amount: Uint128,
+    /// nothing was bonded, the amount is added to the index on the next bond
+    pub deferred: bool,
+}
+
+impl TypedEvent for DistributeNowEvent {
+    const NAME: &'static str = EVENT_DISTRIBUTE_NOW;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("pool_id", self.pool_id.to_string())
+            .add_attribute("asset", &self.asset)
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("deferred", self.deferred.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(DistributeNowEvent {
+            pool_id: required(event, "pool_id")?,
+            asset: required(event, "asset")?,
+            amount: required(event, "amount")?,
+            deferred: required(event, "deferred")?,
+        })
+    }
+}
+
+/// The rewards owed exceeded the reward balance, the schedule emission was halted
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsolvencyAlertEvent {
+    pub reward_balance: Uint128,
+    pub reward_liability: Uint128,
+    pub shortfall: Uint128,
+}
+
+impl TypedEvent for InsolvencyAlertEvent {
+    const NAME: &'static str = EVENT_INSOLVENCY_ALERT;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("reward_balance", self.reward_balance.to_string())
+            .add_attribute("reward_liability", self.reward_liability.to_string())
+            .add_attribute("shortfall", self.shortfall.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(InsolvencyAlertEvent {
+            reward_balance: required(event, "reward_balance")?,
+            reward_liability: required(event, "reward_liability")?,
+            shortfall: required(event, "shortfall")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResumeEmissionEvent {
+    pub last_distributed: u64,
+    /// rewards scheduled while halted, emitted at once
+    pub backlog_amount: Uint128,
+}
+
+impl TypedEvent for ResumeEmissionEvent {
+    const NAME: &'static str = EVENT_RESUME_EMISSION;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("last_distributed", self.last_distributed.to_string())
+            .add_attribute("backlog_amount", self.backlog_amount.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ResumeEmissionEvent {
+            last_distributed: required(event, "last_distributed")?,
+            backlog_amount: required(event, "backlog_amount")?,
+        })
+    }
+}
+
+/// A rolling reward period started, the leftover of the previous one rolled over
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotifyRewardAmountEvent {
+    pub schedule_id: u64,
+    pub amount: Uint128,
+    pub leftover: Uint128,
+    pub period_finish: u64,
+}
+
+impl TypedEvent for NotifyRewardAmountEvent {
+    const NAME: &'static str = EVENT_NOTIFY_REWARD_AMOUNT;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME)
+            .add_attribute("schedule_id", self.schedule_id.to_string())
+            .add_attribute("amount", self.amount.to_string())
+            .add_attribute("leftover", self.leftover.to_string())
+            .add_attribute("period_finish", self.period_finish.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(NotifyRewardAmountEvent {
+            schedule_id: required(event, "schedule_id")?,
+            amount: required(event, "amount")?,
+            leftover: required(event, "leftover")?,
+            period_finish: required(event, "period_finish")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetRewardsDurationEvent {
+    pub rewards_duration: u64,
+}
+
+impl TypedEvent for SetRewardsDurationEvent {
+    const NAME: &'static str = EVENT_SET_REWARDS_DURATION;
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::NAME).add_attribute("rewards_duration", self.rewards_duration.to_string())
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(SetRewardsDurationEvent {
+            rewards_duration: required(event, "rewards_duration")?,
+        })
+    }
+}
+
+/// Any event emitted by the staking contract
+#[derive(Clone, Debug, PartialEq)]
+pub enum StakingEvent {
+    Bond(BondEvent),
+    Unbond(UnbondEvent),
+    Withdraw(WithdrawEvent),
+    Exit(ExitEvent),
+    ChangeOwner(ChangeOwnerEvent),
+    AddRewardSchedule(AddRewardScheduleEvent),
+    ModifyRewardSchedule(ModifyRewardScheduleEvent),
+    MigrateStaking(MigrateStakingEvent),
+    AddPool(AddPoolEvent),
+    SetPool(SetPoolEvent),
+    UpdateBondLimits(UpdateBondLimitsEvent),
+    UpdateAllowlist(UpdateAllowlistEvent),
+    SetRewardVesting(SetRewardVestingEvent),
+    SetDistributor(SetDistributorEvent),
+    UpdateTrustedForwarders(UpdateTrustedForwardersEvent),
+    VestRewards(VestRewardsEvent),
+    ClaimVested(ClaimVestedEvent),
+    AddHook(AddHookEvent),
+    RemoveHook(RemoveHookEvent),
+    Freeze(FreezeEvent),
+    Unfreeze(UnfreezeEvent),
+    DistributeNow(DistributeNowEvent),
+    InsolvencyAlert(InsolvencyAlertEvent),
+    ResumeEmission(ResumeEmissionEvent),
+    NotifyRewardAmount(NotifyRewardAmountEvent),
+    SetRewardsDuration(SetRewardsDurationEvent),
+}
+
+impl StakingEvent {
+    /// Parses a single event, returns None for events of other contracts
+    pub fn parse(event: &Event) -> StdResult<Option<StakingEvent>> {
+        let name = event
+            .ty
+            .strip_prefix(WASM_EVENT_PREFIX)
+            .unwrap_or(&event.ty);
+
+        Ok(Some(match name {
+            EVENT_BOND => StakingEvent::Bond(BondEvent::from_event(event)?),
+            EVENT_UNBOND => StakingEvent::Unbond(UnbondEvent::from_event(event)?),
+            EVENT_WITHDRAW => StakingEvent::Withdraw(WithdrawEvent::from_event(event)?),
+            EVENT_EXIT => StakingEvent::Exit(ExitEvent::from_event(event)?),
+            EVENT_CHANGE_OWNER => StakingEvent::ChangeOwner(ChangeOwnerEvent::from_event(event)?),
+            EVENT_ADD_REWARD_SCHEDULE => {
+                StakingEvent::AddRewardSchedule(AddRewardScheduleEvent::from_event(event)?)
+            }
+            EVENT_MODIFY_REWARD_SCHEDULE => {
+                StakingEvent::ModifyRewardSchedule(ModifyRewardScheduleEvent::from_event(event)?)
+            }
+            EVENT_MIGRATE_STAKING => {
+                StakingEvent::MigrateStaking(MigrateStakingEvent::from_event(event)?)
+            }
+            EVENT_ADD_POOL => StakingEvent::AddPool(AddPoolEvent::from_event(event)?),
+            EVENT_SET_POOL => StakingEvent::SetPool(SetPoolEvent::from_event(event)?),
+            EVENT_UPDATE_BOND_LIMITS => {
+                StakingEvent::UpdateBondLimits(UpdateBondLimitsEvent::from_event(event)?)
+            }
+            EVENT_UPDATE_ALLOWLIST => {
+                StakingEvent::UpdateAllowlist(UpdateAllowlistEvent::from_event(event)?)
+            }
+            EVENT_SET_REWARD_VESTING => {
+                StakingEvent::SetRewardVesting(SetRewardVestingEvent::from_event(event)?)
+            }
+            EVENT_SET_DISTRIBUTOR => {
+                StakingEvent::SetDistributor(SetDistributorEvent::from_event(event)?)
+            }
+            EVENT_UPDATE_TRUSTED_FORWARDERS => StakingEvent::UpdateTrustedForwarders(
+                UpdateTrustedForwardersEvent::from_event(event)?,
+            ),
+            EVENT_VEST_REWARDS => StakingEvent::VestRewards(VestRewardsEvent::from_event(event)?),
+            EVENT_CLAIM_VESTED => StakingEvent::ClaimVested(ClaimVestedEvent::from_event(event)?),
+            EVENT_ADD_HOOK => StakingEvent::AddHook(AddHookEvent::from_event(event)?),
+            EVENT_REMOVE_HOOK => StakingEvent::RemoveHook(RemoveHookEvent::from_event(event)?),
+            EVENT_FREEZE => StakingEvent::Freeze(FreezeEvent::from_event(event)?),
+            EVENT_UNFREEZE => StakingEvent::Unfreeze(UnfreezeEvent::from_event(event)?),
+            EVENT_DISTRIBUTE_NOW => {
+                StakingEvent::DistributeNow(DistributeNowEvent::from_event(event)?)
+            }
+            EVENT_INSOLVENCY_ALERT => {
+                StakingEvent::InsolvencyAlert(InsolvencyAlertEvent::from_event(event)?)
+            }
+            EVENT_RESUME_EMISSION => {
+                StakingEvent::ResumeEmission(ResumeEmissionEvent::from_event(event)?)
+            }
+            EVENT_NOTIFY_REWARD_AMOUNT => {
+                StakingEvent::NotifyRewardAmount(NotifyRewardAmountEvent::from_event(event)?)
+            }
+            EVENT_SET_REWARDS_DURATION => {
+                StakingEvent::SetRewardsDuration(SetRewardsDurationEvent::from_event(event)?)
+            }
+            _ => return Ok(None),
+        }))
+    }
+}
+
+/// Parses the staking events out of a list of events, e.g. `Response::events`
+pub fn parse_events(events: &[Event]) -> StdResult<Vec<StakingEvent>> {
+    events
+        .iter()
+        .filter_map(|event| StakingEvent::parse(event).transpose())
+        .collect()
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+}
+
+fn parse_value<T>(event: &Event, key: &str, value: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse::<T>().map_err(|e| {
+        StdError::parse_err(
+            std::any::type_name::<T>(),
+            format!("{} attribute {}: {}", event.ty, key, e),
+        )
+    })
+}
+
+fn required<T>(event: &Event, key: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = attribute(event, key).ok_or_else(|| {
+        StdError::generic_err(format!("{} is missing attribute {}", event.ty, key))
+    })?;
+    parse_value(event, key, value)
+}
+
+fn optional<T>(event: &Event, key: &str) -> StdResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    attribute(event, key)
+        .map(|value| parse_value(event, key, value))
+        .transpose()
+}
+
+fn list(event: &Event, key: &str) -> StdResult<Vec<String>> {
+    let value: String = required(event, key)?;
+    Ok(value
+        .split(',')
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect())
+}
+
+fn with_optional<T: ToString>(event: Event, key: &str, value: &Option<T>) -> Event {
+    match value {
+        Some(value) => event.add_attribute(key, value.to_string()),
+        None => event,
+    }
+}
@@ -1,8 +1,11 @@
 use crate::mock_querier::mock_dependencies;
 use crate::querier::{compute_tax, deduct_tax, query_tax_rate};
+use crate::staking::events::{
+    parse_events, BondEvent, StakingEvent, TypedEvent, UnbondEvent, EVENT_BOND,
+};
 
 use cosmwasm_bignumber::{Decimal256, Uint256};
-use cosmwasm_std::{Coin, Decimal, Uint128};
+use cosmwasm_std::{Coin, Decimal, Event, StdError, Uint128};
 
 #[test]
 fn tax_rate_querier() {
@@ -64,3 +67,47 @@ fn test_deduct_tax() {
         }
     );
 }
+
+#[test]
+fn test_parse_staking_events() {
+    let bond = BondEvent {
+        pool_id: 0,
+        staker: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        bond_amount: Uint128::from(150u128),
+        total_bond_amount: Uint128::from(1000u128),
+        global_reward_index: Decimal::percent(250),
+    };
+    let unbond = UnbondEvent {
+        pool_id: 1,
+        staker: "addr0000".to_string(),
+        amount: Uint128::from(50u128),
+        reward_amount: Uint128::zero(),
+        bond_amount: Uint128::from(100u128),
+        total_bond_amount: Uint128::from(950u128),
+        global_reward_index: Decimal::percent(250),
+        contract: Some("contract0000".to_string()),
+    };
+
+    // event types are prefixed by the chain
+    let mut wasm_unbond = unbond.to_event();
+    wasm_unbond.ty = format!("wasm-{}", wasm_unbond.ty);
+
+    let events = vec![
+        Event::new("transfer").add_attribute("amount", "100"),
+        bond.to_event(),
+        wasm_unbond,
+    ];
+    assert_eq!(
+        parse_events(&events).unwrap(),
+        vec![StakingEvent::Bond(bond), StakingEvent::Unbond(unbond)]
+    );
+
+    let event = Event::new(EVENT_BOND).add_attribute("pool_id", "0");
+    match parse_events(&[event]) {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "xdefi_staking_bond is missing attribute staker")
+        }
+        _ => panic!("Must return generic error"),
+    }
+}
@@ -19,23 +19,43 @@ pub enum ExecuteMsg {
         anchor_token: Option<String>,
         genesis_time: Option<u64>,
     },
+    /// Registers new vesting accounts, an address already registered is rejected
+    /// so its claimed amount can't be released again
     RegisterVestingAccounts {
         vesting_accounts: Vec<VestingAccount>,
     },
+    /// Owner operation to cancel a revocable vesting account, the vested amount
+    /// is sent to the account and the unvested amount back to the owner
+    RevokeVestingAccount {
+        address: String,
+    },
     Claim {},
 }
 
+/// We currently take no arguments for migrations
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 /// CONTRACT: end_time > start_time
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VestingAccount {
     pub address: String,
     pub schedules: Vec<(u64, u64, Uint128)>,
+    /// nothing can be claimed before the cliff time,
+    /// the amount vested until then is released at once
+    pub cliff: Option<u64>,
+    /// allows the owner to revoke the unvested amount, false by default
+    pub revocable: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VestingInfo {
     pub schedules: Vec<(u64, u64, Uint128)>,
     pub last_claim_time: u64,
+    #[serde(default)]
+    pub cliff: Option<u64>,
+    #[serde(default)]
+    pub revocable: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -2,21 +2,61 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, CanonicalAddr, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Api, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Env, Event, MessageInfo, OverflowError, OverflowOperation, Response,
+    StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 
 use xdefi_token::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    StakerInfoResponse, StateResponse,
+    AddRewardResponse, AllowlistResponse, CapacityResponse, ConfigResponse, Cw20HookMsg,
+    EmissionCurve, ExecuteMsg, FrozenStakersResponse, HooksResponse, InstantiateMsg, MigrateMsg,
+    PeriodFinishResponse, PoolResponse, PoolsResponse, QueryMsg, RewardSchedule,
+    RewardVestingResponse, SolvencyResponse, StakeChangedHookMsg, StakerInfoResponse,
+    StakerInfosResponse, StakingTokenSolvency, StateResponse, TopStakerResponse,
+    TopStakersResponse, DEFAULT_POOL_ID, MAX_STAKER_INFOS,
 };
 
+use xdefi_token::staking::events::{
+    AddHookEvent, AddPoolEvent, AddRewardScheduleEvent, BondEvent, ChangeOwnerEvent,
+    ClaimVestedEvent, DistributeNowEvent, ExitEvent, FreezeEvent, InsolvencyAlertEvent,
+    MigrateStakingEvent, ModifyRewardScheduleEvent, NotifyRewardAmountEvent, RemoveHookEvent,
+    ResumeEmissionEvent, SetDistributorEvent, SetPoolEvent, SetRewardVestingEvent,
+    SetRewardsDurationEvent, TypedEvent, UnbondEvent, UnfreezeEvent, UpdateAllowlistEvent,
+    UpdateBondLimitsEvent, UpdateTrustedForwardersEvent, VestRewardsEvent, WithdrawEvent,
+};
+
+use xdefi_token::asset::{AssetInfo, AssetInfoRaw};
+use xdefi_token::distributor::ExecuteMsg as DistributorExecuteMsg;
+use xdefi_token::gov::Cw20HookMsg as GovCw20HookMsg;
+use xdefi_token::vesting::VestingInfo;
+
+use crate::emission::{emitted_amount, validate_curve};
+use crate::migration::{
+    read_legacy_config, read_legacy_staker_infos, read_legacy_state, remove_legacy_staker_info,
+    LegacyConfig,
+};
 use crate::state::{
-    read_config, read_staker_info, read_state, remove_staker_info, store_config, store_staker_info,
-    store_state, Config, StakerInfo, State,
+    increase_staker_claimed, is_allowlisted, is_frozen, read_allowlist,
+    read_archived_reward_schedules, read_config, read_frozen, read_hooks, read_pool_info,
+    read_pool_info_at, read_pools, read_reward_schedules, read_reward_schedules_between,
+    read_reward_vesting, read_staker_claimed, read_staker_info, read_staker_info_at, read_state,
+    read_state_at, read_top_stakers, remove_allowlisted, remove_frozen, remove_reward_schedule,
+    remove_reward_vesting, remove_staker_info, store_allowlisted, store_config, store_frozen,
+    store_hooks, store_pool_info, store_reward_schedule, store_reward_vesting, store_staker_info,
+    store_state, BondLimits, Config, PoolInfo, StakerInfo, State, CHECKPOINT_RETENTION,
 };
 
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+
+// allocation point of the pool created at instantiation
+const DEFAULT_ALLOC_POINT: u64 = 1;
+
+// every hook adds a message to each bond change, so keep their number bounded
+const MAX_HOOKS: usize = 10;
+
+// number of stakers moved by a `MigrateStakers` batch
+const DEFAULT_MIGRATE_LIMIT: u32 = 30;
+const MAX_MIGRATE_LIMIT: u32 = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -25,23 +65,71 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    for (id, (start, end, amount)) in msg.distribution_schedule.iter().enumerate() {
+        store_reward_schedule(
+            deps.storage,
+            &RewardSchedule {
+                id: id as u64,
+                start: *start,
+                end: *end,
+                amount: *amount,
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+        )?;
+    }
+
     store_config(
         deps.storage,
         &Config {
             xdefi_token: deps.api.addr_canonicalize(&msg.xdefi_token)?,
-            staking_token: deps.api.addr_canonicalize(&msg.staking_token)?,
-            distribution_schedule: msg.distribution_schedule,
+            next_schedule_id: msg.distribution_schedule.len() as u64,
+            allowlist_enabled: false,
+            reward_vesting_period: None,
+            distributor_contract: None,
+            trusted_forwarders: vec![],
+            rewards_duration: 0,
+            period_finish: 0,
+            rolling_schedule_id: None,
+        },
+    )?;
+
+    // the default pool receives the whole distribution until other pools are added
+    store_pool_info(
+        deps.storage,
+        DEFAULT_POOL_ID,
+        &PoolInfo {
+            staking_token: msg.staking_token.to_raw(deps.api)?,
+            alloc_point: DEFAULT_ALLOC_POINT,
+            total_bond_amount: Uint128::zero(),
+            global_reward_index: Decimal::zero(),
+            reward_per_alloc_point: Decimal::zero(),
+            bond_limits: BondLimits::default(),
+            undistributed_revenue: Uint128::zero(),
+            native_reward_indexes: vec![],
+            undistributed_native_revenue: vec![],
         },
+        env.block.height,
     )?;
 
     store_state(
         deps.storage,
         &State {
             last_distributed: env.block.height,
-            total_bond_amount: Uint128::zero(),
-            global_reward_index: Decimal::zero(),
+            total_alloc_point: DEFAULT_ALLOC_POINT,
+            reward_per_alloc_point: Decimal::zero(),
+            pool_count: 1,
             owner_address: deps.api.addr_canonicalize(info.sender.as_str())?,
+            total_distributed: Uint128::zero(),
+            uncredited_rewards: Uint128::zero(),
+            total_claimed: Uint128::zero(),
+            total_vesting: Uint128::zero(),
+            emission_halted: false,
+            halted_since: 0,
+            total_bonded_reward_token: Uint128::zero(),
+            legacy_reward_index: None,
         },
+        env.block.height,
     )?;
 
     Ok(Response::default())
@@ -51,489 +139,2929 @@ pub fn instantiate(
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
-        ExecuteMsg::Unbond { amount } => unbond(deps, env, info, amount),
-        ExecuteMsg::Withdraw {} => withdraw(deps, env, info),
+        ExecuteMsg::BondNative { pool_id, staker } => {
+            bond_native(deps, env, info, pool_id.unwrap_or(DEFAULT_POOL_ID), staker)
+        }
+        ExecuteMsg::Unbond {
+            pool_id,
+            amount,
+            claim_rewards,
+        } => unbond(
+            deps,
+            env,
+            info,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            amount,
+            claim_rewards.unwrap_or(false),
+            None,
+        ),
+        ExecuteMsg::UnbondTo {
+            pool_id,
+            amount,
+            contract,
+            msg,
+        } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            unbond(
+                deps,
+                env,
+                info,
+                pool_id.unwrap_or(DEFAULT_POOL_ID),
+                amount,
+                false,
+                Some((contract, msg)),
+            )
+        }
+        ExecuteMsg::Withdraw { pool_id } => {
+            withdraw(deps, env, info, pool_id.unwrap_or(DEFAULT_POOL_ID), None)
+        }
+        ExecuteMsg::WithdrawTo {
+            pool_id,
+            contract,
+            msg,
+        } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            withdraw(
+                deps,
+                env,
+                info,
+                pool_id.unwrap_or(DEFAULT_POOL_ID),
+                Some((contract, msg)),
+            )
+        }
+        ExecuteMsg::Exit { pool_id } => exit(deps, env, info, pool_id.unwrap_or(DEFAULT_POOL_ID)),
         ExecuteMsg::MigrateStaking {
             new_staking_contract,
         } => migrate_staking(deps, env, info, new_staking_contract),
         ExecuteMsg::ChangeOwner { new_owner_address } => {
             change_owner(deps, env, info, new_owner_address)
         }
-        ExecuteMsg::AddReward { reward_schedule } => {
-            add_reward_schedule(deps, env, info, reward_schedule)
-        }
+        ExecuteMsg::AddReward {
+            reward_schedule,
+            label,
+            curve,
+        } => add_reward_schedule(deps, env, info, reward_schedule, label, curve),
         ExecuteMsg::ModifyReward {
             new_emission,
-            index_schedule,
-        } => modify_reward_schedule(deps, env, info, new_emission, index_schedule),
+            schedule_id,
+        } => modify_reward_schedule(deps, env, info, new_emission, schedule_id),
+        ExecuteMsg::AddPool {
+            staking_token,
+            alloc_point,
+        } => add_pool(deps, env, info, staking_token, alloc_point),
+        ExecuteMsg::SetPool {
+            pool_id,
+            alloc_point,
+        } => set_pool(deps, env, info, pool_id, alloc_point),
+        ExecuteMsg::UpdateAllowlist {
+            enabled,
+            add,
+            remove,
+        } => update_allowlist(deps, info, enabled, add, remove),
+        ExecuteMsg::UpdateBondLimits {
+            pool_id,
+            max_total_bond,
+            max_bond_per_staker,
+            min_bond_amount,
+        } => update_bond_limits(
+            deps,
+            env,
+            info,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            BondLimits {
+                max_total_bond,
+                max_bond_per_staker,
+                min_bond_amount,
+            },
+        ),
+        ExecuteMsg::SetRewardVesting { period } => set_reward_vesting(deps, info, period),
+        ExecuteMsg::SetDistributor {
+            distributor_contract,
+        } => set_distributor(deps, info, distributor_contract),
+        ExecuteMsg::UpdateTrustedForwarders { add, remove } => {
+            update_trusted_forwarders(deps, info, add, remove)
+        }
+        ExecuteMsg::ClaimVested {} => claim_vested(deps, env, info),
+        ExecuteMsg::AddHook { addr } => add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => remove_hook(deps, info, addr),
+        ExecuteMsg::Freeze { staker } => freeze(deps, info, staker),
+        ExecuteMsg::Unfreeze { staker } => unfreeze(deps, info, staker),
+        ExecuteMsg::DistributeNativeNow { pool_id } => {
+            distribute_native_now(deps, env, info, pool_id.unwrap_or(DEFAULT_POOL_ID))
+        }
+        ExecuteMsg::ResumeEmission {} => resume_emission(deps, env, info),
+        ExecuteMsg::MigrateStakers { limit } => migrate_stakers(deps, env, limit),
+        ExecuteMsg::SetRewardsDuration { rewards_duration } => {
+            set_rewards_duration(deps, env, info, rewards_duration)
+        }
     }
 }
 
-pub fn modify_reward_schedule(
+pub fn add_pool(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    new_emission: Uint128,
-    index_schedule: usize,
+    staking_token: AssetInfo,
+    alloc_point: u64,
 ) -> StdResult<Response> {
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-    let state: State = read_state(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
 
-    // get gov address by querying owner address
-    let gov_addr_raw: CanonicalAddr = state.owner_address;
-    if sender_addr_raw != gov_addr_raw {
+    if sender_addr_raw != state.owner_address {
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    let mut config: Config = read_config(deps.storage)?;
+    // a staking token is bonded in a single pool
+    let staking_token = staking_token.to_raw(deps.api)?;
+    for pool_id in 0..state.pool_count {
+        if read_pool_info(deps.storage, pool_id)?.staking_token == staking_token {
+            return Err(StdError::generic_err(
+                "a pool already exists for the staking token",
+            ));
+        }
+    }
 
-    let block_height = env.block.height;
+    let total_alloc_point = state
+        .total_alloc_point
+        .checked_add(alloc_point)
+        .ok_or_else(|| {
+            OverflowError::new(OverflowOperation::Add, state.total_alloc_point, alloc_point)
+        })?;
 
-    let mut actual_distribution_schedule = config.distribution_schedule.clone();
+    // distribute with the previous weights before adding the new pool
+    compute_reward(deps.storage, &mut state, env.block.height)?;
 
-    if index_schedule >= actual_distribution_schedule.len() {
-        return Err(StdError::generic_err("index out of schedule array range"));
-    }
+    let pool_id = state.pool_count;
+    store_pool_info(
+        deps.storage,
+        pool_id,
+        &PoolInfo {
+            staking_token,
+            alloc_point,
+            total_bond_amount: Uint128::zero(),
+            global_reward_index: Decimal::zero(),
+            reward_per_alloc_point: state.reward_per_alloc_point,
+            bond_limits: BondLimits::default(),
+            undistributed_revenue: Uint128::zero(),
+            native_reward_indexes: vec![],
+            undistributed_native_revenue: vec![],
+        },
+        env.block.height,
+    )?;
+
+    state.pool_count += 1;
+    state.total_alloc_point = total_alloc_point;
+    store_state(deps.storage, &state, env.block.height)?;
 
-    let mut to_modify_schedule = actual_distribution_schedule[index_schedule];
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "add_pool"),
+            ("pool_id", &pool_id.to_string()),
+            ("alloc_point", &alloc_point.to_string()),
+        ])
+        .add_event(
+            AddPoolEvent {
+                pool_id,
+                alloc_point,
+                total_alloc_point: state.total_alloc_point,
+            }
+            .to_event(),
+        ))
+}
 
-    let reward_schedule_minimum_block = std::cmp::min(to_modify_schedule.0, to_modify_schedule.1);
+pub fn set_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    alloc_point: u64,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut state: State = read_state(deps.storage)?;
 
-    if block_height > reward_schedule_minimum_block {
-        return Err(StdError::generic_err(
-            "cannot modify a schedule that was passed already",
-        ));
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
     }
 
-    if new_emission == Uint128::from(0u128) {
-        return Err(StdError::generic_err("Reward has to be greater than 0"));
-    }
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+
+    let remaining_alloc_point = state
+        .total_alloc_point
+        .checked_sub(pool_info.alloc_point)
+        .ok_or_else(|| {
+            OverflowError::new(
+                OverflowOperation::Sub,
+                state.total_alloc_point,
+                pool_info.alloc_point,
+            )
+        })?;
+    let total_alloc_point = remaining_alloc_point
+        .checked_add(alloc_point)
+        .ok_or_else(|| {
+            OverflowError::new(OverflowOperation::Add, remaining_alloc_point, alloc_point)
+        })?;
+
+    // distribute with the previous weights before re-weighting the pool
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+
+    state.total_alloc_point = total_alloc_point;
+    pool_info.alloc_point = alloc_point;
+
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+    store_state(deps.storage, &state, env.block.height)?;
 
-    *&mut to_modify_schedule.2 = new_emission;
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "set_pool"),
+            ("pool_id", &pool_id.to_string()),
+            ("alloc_point", &alloc_point.to_string()),
+        ])
+        .add_event(
+            SetPoolEvent {
+                pool_id,
+                alloc_point,
+                total_alloc_point: state.total_alloc_point,
+            }
+            .to_event(),
+        ))
+}
 
-    *&mut actual_distribution_schedule[index_schedule] = to_modify_schedule;
+pub fn update_bond_limits(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    bond_limits: BondLimits,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
 
-    *&mut config.distribution_schedule = actual_distribution_schedule.clone();
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
-    // update config
-    store_config(deps.storage, &config)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    pool_info.bond_limits = bond_limits;
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "modify_reward_schedule"),
-        ("index_schedule", &index_schedule.to_string()),
-        ("new_emission", &new_emission.to_string()),
-    ]))
+    let limits = &pool_info.bond_limits;
+    let limit_to_string =
+        |limit: Option<Uint128>| limit.map_or_else(|| "none".to_string(), |l| l.to_string());
+    Ok(Response::new()
+        .add_event(
+            UpdateBondLimitsEvent {
+                pool_id,
+                max_total_bond: limits.max_total_bond,
+                max_bond_per_staker: limits.max_bond_per_staker,
+                min_bond_amount: limits.min_bond_amount,
+            }
+            .to_event(),
+        )
+        .add_attributes(vec![
+            ("action", "update_bond_limits".to_string()),
+            ("pool_id", pool_id.to_string()),
+            ("max_total_bond", limit_to_string(limits.max_total_bond)),
+            (
+                "max_bond_per_staker",
+                limit_to_string(limits.max_bond_per_staker),
+            ),
+            ("min_bond_amount", limit_to_string(limits.min_bond_amount)),
+        ]))
 }
 
-pub fn add_reward_schedule(
+pub fn update_allowlist(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    reward_schedule: (u64, u64, Uint128),
+    enabled: Option<bool>,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
 ) -> StdResult<Response> {
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
     let state: State = read_state(deps.storage)?;
 
-    // get gov address by querying owner address
-    let gov_addr_raw: CanonicalAddr = state.owner_address;
-    if sender_addr_raw != gov_addr_raw {
+    if sender_addr_raw != state.owner_address {
         return Err(StdError::generic_err("unauthorized"));
     }
 
     let mut config: Config = read_config(deps.storage)?;
-    let block_height = env.block.height;
-    let mut actual_distribution_schedule = config.distribution_schedule.clone();
+    if let Some(enabled) = enabled {
+        config.allowlist_enabled = enabled;
+        store_config(deps.storage, &config)?;
+    }
 
-    let reward_schedule_minimum_block = std::cmp::min(reward_schedule.0, reward_schedule.1);
+    let added = add.unwrap_or_default();
+    for addr in added.iter() {
+        store_allowlisted(deps.storage, &deps.api.addr_canonicalize(addr)?)?;
+    }
 
-    if block_height > reward_schedule_minimum_block {
-        return Err(StdError::generic_err(
-            "cannot add a schedule that was passed already",
-        ));
+    let removed = remove.unwrap_or_default();
+    for addr in removed.iter() {
+        remove_allowlisted(deps.storage, &deps.api.addr_canonicalize(addr)?);
     }
 
-    if reward_schedule.0 >= reward_schedule.1 {
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "update_allowlist"),
+            ("enabled", &config.allowlist_enabled.to_string()),
+        ])
+        .add_event(
+            UpdateAllowlistEvent {
+                enabled: config.allowlist_enabled,
+                added,
+                removed,
+            }
+            .to_event(),
+        ))
+}
+
+pub fn set_reward_vesting(
+    deps: DepsMut,
+    info: MessageInfo,
+    period: Option<u64>,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if period == Some(0) {
         return Err(StdError::generic_err(
-            "End schedule block has to be greater than beginning block",
+            "Vesting period has to be greater than 0",
         ));
     }
 
-    if reward_schedule.2 == Uint128::from(0u128) {
-        return Err(StdError::generic_err("Reward has to be greater than 0"));
-    }
+    // rewards already in the vesting ledger keep their schedules
+    let mut config: Config = read_config(deps.storage)?;
+    config.reward_vesting_period = period;
+    store_config(deps.storage, &config)?;
 
-    //we finally use that instead of max existing block check on schedule, so we avoid having to migrate the contract if a mistake was done in putting a period too far away
-    for schedule in actual_distribution_schedule.clone() {
-        if reward_schedule.0 >= schedule.0 && reward_schedule.0 <= schedule.1
-            || reward_schedule.1 >= schedule.0 && reward_schedule.1 <= schedule.1
-        {
-            return Err(StdError::generic_err(
-                "The new reward schedule has to be a new period, the period is overtaking an existing upcoming schedule period",
-            ));
-        }
-    }
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "set_reward_vesting".to_string()),
+            (
+                "period",
+                period.map_or_else(|| "none".to_string(), |p| p.to_string()),
+            ),
+        ])
+        .add_event(SetRewardVestingEvent { period }.to_event()))
+}
 
-    actual_distribution_schedule.push(reward_schedule);
+pub fn set_distributor(
+    deps: DepsMut,
+    info: MessageInfo,
+    distributor_contract: Option<String>,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
 
-    *&mut config.distribution_schedule = actual_distribution_schedule.clone();
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
-    // update config
+    let mut config: Config = read_config(deps.storage)?;
+    config.distributor_contract = match &distributor_contract {
+        Some(addr) => Some(deps.api.addr_canonicalize(addr)?),
+        None => None,
+    };
     store_config(deps.storage, &config)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "add_reward_schedule"),
-        ("starting_block", &reward_schedule.0.to_string()),
-        ("end_block", &reward_schedule.1.to_string()),
-        ("total_emission", &reward_schedule.2.to_string()),
-    ]))
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "set_distributor"),
+            (
+                "distributor_contract",
+                distributor_contract.as_deref().unwrap_or("none"),
+            ),
+        ])
+        .add_event(
+            SetDistributorEvent {
+                distributor_contract,
+            }
+            .to_event(),
+        ))
 }
 
-pub fn receive_cw20(
+pub fn update_trusted_forwarders(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
-    cw20_msg: Cw20ReceiveMsg,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
 ) -> StdResult<Response> {
-    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
 
-    match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::Bond {}) => {
-            // only staking token contract can execute this message
-            if config.staking_token != deps.api.addr_canonicalize(info.sender.as_str())? {
-                return Err(StdError::generic_err("unauthorized"));
-            }
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
-            let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
-            bond(deps, env, cw20_sender, cw20_msg.amount)
+    let mut config: Config = read_config(deps.storage)?;
+    let added = add.unwrap_or_default();
+    for addr in added.iter() {
+        let addr_raw = deps.api.addr_canonicalize(addr)?;
+        if !config.trusted_forwarders.contains(&addr_raw) {
+            config.trusted_forwarders.push(addr_raw);
         }
-        Err(_) => Err(StdError::generic_err("data should be given")),
     }
-}
-
-pub fn bond(deps: DepsMut, env: Env, sender_addr: Addr, amount: Uint128) -> StdResult<Response> {
-    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(sender_addr.as_str())?;
-
-    let config: Config = read_config(deps.storage)?;
-    let mut state: State = read_state(deps.storage)?;
-    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
 
-    // Compute global reward & staker reward
-    compute_reward(&config, &mut state, env.block.height);
-    compute_staker_reward(&state, &mut staker_info)?;
+    let removed = remove.unwrap_or_default();
+    for addr in removed.iter() {
+        let addr_raw = deps.api.addr_canonicalize(addr)?;
+        config
+            .trusted_forwarders
+            .retain(|forwarder| *forwarder != addr_raw);
+    }
+    store_config(deps.storage, &config)?;
 
-    // Increase bond_amount
-    increase_bond_amount(&mut state, &mut staker_info, amount);
+    Ok(Response::new()
+        .add_attributes(vec![("action", "update_trusted_forwarders")])
+        .add_event(UpdateTrustedForwardersEvent { added, removed }.to_event()))
+}
 
-    // Store updated state with staker's staker_info
-    store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
-    store_state(deps.storage, &state)?;
+// this contract forwards unbonded tokens itself, the others have to be trusted by the owner
+fn is_trusted_forwarder(deps: Deps, env: &Env, sender: &str) -> StdResult<bool> {
+    if sender == env.contract.address.as_str() {
+        return Ok(true);
+    }
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "bond"),
-        ("owner", sender_addr.as_str()),
-        ("amount", amount.to_string().as_str()),
-    ]))
+    let config: Config = read_config(deps.storage)?;
+    Ok(config
+        .trusted_forwarders
+        .contains(&deps.api.addr_canonicalize(sender)?))
 }
 
-pub fn unbond(deps: DepsMut, env: Env, info: MessageInfo, amount: Uint128) -> StdResult<Response> {
-    let config: Config = read_config(deps.storage)?;
+pub fn claim_vested(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_not_frozen(deps.as_ref(), &sender_addr_raw)?;
 
+    let config: Config = read_config(deps.storage)?;
     let mut state: State = read_state(deps.storage)?;
-    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+    let mut vesting_info: VestingInfo = read_reward_vesting(deps.storage, &sender_addr_raw)?
+        .ok_or_else(|| StdError::generic_err("No vesting rewards"))?;
 
-    if staker_info.bond_amount < amount {
-        return Err(StdError::generic_err("Cannot unbond more than bond amount"));
+    let block_height = env.block.height;
+    let amount = compute_vested_amount(&vesting_info, block_height);
+    if amount.is_zero() {
+        return Err(StdError::generic_err("No vested rewards to claim"));
     }
 
-    // Compute global reward & staker reward
-    compute_reward(&config, &mut state, env.block.height);
-    compute_staker_reward(&state, &mut staker_info)?;
-
-    // Decrease bond_amount
-    decrease_bond_amount(&mut state, &mut staker_info, amount)?;
-
-    // Store or remove updated rewards info
-    // depends on the left pending reward and bond amount
-    if staker_info.pending_reward.is_zero() && staker_info.bond_amount.is_zero() {
-        remove_staker_info(deps.storage, &sender_addr_raw);
+    // drop the fully released schedules
+    vesting_info.last_claim_time = block_height;
+    vesting_info.schedules.retain(|s| s.1 > block_height);
+    if vesting_info.schedules.is_empty() {
+        remove_reward_vesting(deps.storage, &sender_addr_raw);
     } else {
-        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+        store_reward_vesting(deps.storage, &sender_addr_raw, &vesting_info)?;
     }
 
-    // Store updated state
-    store_state(deps.storage, &state)?;
+    // ledgers created before the vesting total was tracked are not counted in it
+    state.total_vesting = state.total_vesting.saturating_sub(amount);
+    store_state(deps.storage, &state, env.block.height)?;
 
     Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.staking_token)?.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })])
+        .add_messages(reward_payout_msgs(
+            deps.api,
+            &env,
+            &config,
+            &info.sender,
+            amount,
+            None,
+        )?)
         .add_attributes(vec![
-            ("action", "unbond"),
+            ("action", "claim_vested"),
             ("owner", info.sender.as_str()),
             ("amount", amount.to_string().as_str()),
-        ]))
+        ])
+        .add_event(
+            ClaimVestedEvent {
+                staker: info.sender.to_string(),
+                amount,
+            }
+            .to_event(),
+        ))
 }
 
-// withdraw rewards to executor
-pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
-    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
-
-    let config: Config = read_config(deps.storage)?;
+pub fn resume_emission(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut state: State = read_state(deps.storage)?;
-    let mut staker_info = read_staker_info(deps.storage, &sender_addr_raw)?;
 
-    // Compute global reward & staker reward
-    compute_reward(&config, &mut state, env.block.height);
-    compute_staker_reward(&state, &mut staker_info)?;
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
-    let amount = staker_info.pending_reward;
-    staker_info.pending_reward = Uint128::zero();
+    if !state.emission_halted {
+        return Err(StdError::generic_err("Emission is not halted"));
+    }
 
-    // Store or remove updated rewards info
-    // depends on the left pending reward and bond amount
-    if staker_info.bond_amount.is_zero() {
-        remove_staker_info(deps.storage, &sender_addr_raw);
-    } else {
-        store_staker_info(deps.storage, &sender_addr_raw, &staker_info)?;
+    // nothing was emitted while halted, sets last_distributed to env.block.height
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+
+    // the rewards scheduled while halted are owed from now on, so they have to be covered
+    let config: Config = read_config(deps.storage)?;
+    let (reward_balance, pending_rewards, unemitted_rewards) =
+        compute_reward_solvency(deps.as_ref(), &env, &config, &state)?;
+    if pending_rewards.checked_add(unemitted_rewards)? > reward_balance {
+        return Err(StdError::generic_err(
+            "Reward balance does not cover the rewards owed",
+        ));
     }
 
-    // Store updated state
-    store_state(deps.storage, &state)?;
+    // emit the backlog at once
+    let mut backlog_amount = Uint128::zero();
+    if state.total_alloc_point != 0 {
+        for s in
+            read_reward_schedules_between(deps.storage, state.halted_since, state.last_distributed)?
+                .iter()
+        {
+            backlog_amount += emitted_amount(s, state.halted_since, state.last_distributed);
+        }
+
+        state.uncredited_rewards += backlog_amount;
+        state.reward_per_alloc_point = state.reward_per_alloc_point
+            + Decimal::from_ratio(backlog_amount, state.total_alloc_point);
+    }
+
+    state.emission_halted = false;
+    store_state(deps.storage, &state, env.block.height)?;
 
     Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps.api.addr_humanize(&config.xdefi_token)?.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })])
         .add_attributes(vec![
-            ("action", "withdraw"),
-            ("owner", info.sender.as_str()),
-            ("amount", amount.to_string().as_str()),
-        ]))
+            ("action", "resume_emission"),
+            ("last_distributed", &state.last_distributed.to_string()),
+            ("backlog_amount", &backlog_amount.to_string()),
+        ])
+        .add_event(
+            ResumeEmissionEvent {
+                last_distributed: state.last_distributed,
+                backlog_amount,
+            }
+            .to_event(),
+        ))
 }
 
-pub fn change_owner(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    new_owner: String,
-) -> StdResult<Response> {
+pub fn add_hook(deps: DepsMut, info: MessageInfo, addr: String) -> StdResult<Response> {
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-    let mut state: State = read_state(deps.storage)?;
+    let state: State = read_state(deps.storage)?;
 
-    // get gov address by querying owner address
-    let gov_addr_raw: CanonicalAddr = state.owner_address;
-    if sender_addr_raw != gov_addr_raw {
+    if sender_addr_raw != state.owner_address {
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    state.owner_address = deps.api.addr_canonicalize(&new_owner)?;
-    // update state with new owner address
-    store_state(deps.storage, &state)?;
+    let hook_raw = deps.api.addr_canonicalize(&addr)?;
+    let mut hooks: Vec<CanonicalAddr> = read_hooks(deps.storage)?;
+    if hooks.contains(&hook_raw) {
+        return Err(StdError::generic_err("hook already registered"));
+    }
+
+    if hooks.len() >= MAX_HOOKS {
+        return Err(StdError::generic_err(format!(
+            "cannot register more than {} hooks",
+            MAX_HOOKS
+        )));
+    }
+
+    hooks.push(hook_raw);
+    store_hooks(deps.storage, &hooks)?;
 
     Ok(Response::new()
-        .add_attribute("method", "change_owner")
-        .add_attribute("new_owner", new_owner.to_string()))
+        .add_attributes(vec![("action", "add_hook"), ("addr", &addr)])
+        .add_event(AddHookEvent { addr }.to_event()))
 }
 
-pub fn migrate_staking(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    new_staking_contract: String,
-) -> StdResult<Response> {
+pub fn remove_hook(deps: DepsMut, info: MessageInfo, addr: String) -> StdResult<Response> {
     let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
-    let mut config: Config = read_config(deps.storage)?;
-    let mut state: State = read_state(deps.storage)?;
-    let anc_token: Addr = deps.api.addr_humanize(&config.xdefi_token)?;
+    let state: State = read_state(deps.storage)?;
 
-    // get gov address by querying owner address
-    let gov_addr_raw: CanonicalAddr = state.owner_address.clone();
-    if sender_addr_raw != gov_addr_raw {
+    if sender_addr_raw != state.owner_address {
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    // compute global reward, sets last_distributed_height to env.block.height
-    compute_reward(&config, &mut state, env.block.height);
+    let hook_raw = deps.api.addr_canonicalize(&addr)?;
+    let mut hooks: Vec<CanonicalAddr> = read_hooks(deps.storage)?;
+    if !hooks.contains(&hook_raw) {
+        return Err(StdError::generic_err("hook not registered"));
+    }
 
-    let total_distribution_amount: Uint128 =
-        config.distribution_schedule.iter().map(|item| item.2).sum();
+    hooks.retain(|hook| *hook != hook_raw);
+    store_hooks(deps.storage, &hooks)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![("action", "remove_hook"), ("addr", &addr)])
+        .add_event(RemoveHookEvent { addr }.to_event()))
+}
+
+pub fn freeze(deps: DepsMut, info: MessageInfo, staker: String) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    store_frozen(deps.storage, &deps.api.addr_canonicalize(&staker)?)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![("action", "freeze"), ("staker", &staker)])
+        .add_event(FreezeEvent { staker }.to_event()))
+}
+
+pub fn unfreeze(deps: DepsMut, info: MessageInfo, staker: String) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    remove_frozen(deps.storage, &deps.api.addr_canonicalize(&staker)?);
+
+    Ok(Response::new()
+        .add_attributes(vec![("action", "unfreeze"), ("staker", &staker)])
+        .add_event(UnfreezeEvent { staker }.to_event()))
+}
+
+pub fn modify_reward_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_emission: Uint128,
+    schedule_id: u64,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    // get gov address by querying owner address
+    let gov_addr_raw: CanonicalAddr = state.owner_address;
+    if sender_addr_raw != gov_addr_raw {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
     let block_height = env.block.height;
+
+    // only the schedules not started yet can be modified, none of them is archived
+    let mut to_modify_schedule = read_reward_schedules(deps.storage)?
+        .into_iter()
+        .find(|schedule| schedule.id == schedule_id)
+        .ok_or_else(|| StdError::generic_err("Reward schedule not found"))?;
+
+    let reward_schedule_minimum_block =
+        std::cmp::min(to_modify_schedule.start, to_modify_schedule.end);
+
+    if block_height > reward_schedule_minimum_block {
+        return Err(StdError::generic_err(
+            "cannot modify a schedule that was passed already",
+        ));
+    }
+
+    if new_emission == Uint128::from(0u128) {
+        return Err(StdError::generic_err("Reward has to be greater than 0"));
+    }
+
+    to_modify_schedule.amount = new_emission;
+    let event = ModifyRewardScheduleEvent {
+        schedule_id,
+        start_block: to_modify_schedule.start,
+        end_block: to_modify_schedule.end,
+        amount: to_modify_schedule.amount,
+    };
+
+    store_reward_schedule(deps.storage, &to_modify_schedule)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "modify_reward_schedule"),
+            ("schedule_id", &schedule_id.to_string()),
+            ("new_emission", &new_emission.to_string()),
+        ])
+        .add_event(event.to_event()))
+}
+
+pub fn add_reward_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_schedule: (u64, u64, Uint128),
+    label: Option<String>,
+    curve: Option<EmissionCurve>,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    // get gov address by querying owner address
+    let gov_addr_raw: CanonicalAddr = state.owner_address;
+    if sender_addr_raw != gov_addr_raw {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut config: Config = read_config(deps.storage)?;
+    let block_height = env.block.height;
+
+    let reward_schedule_minimum_block = std::cmp::min(reward_schedule.0, reward_schedule.1);
+
+    if block_height > reward_schedule_minimum_block {
+        return Err(StdError::generic_err(
+            "cannot add a schedule that was passed already",
+        ));
+    }
+
+    if reward_schedule.0 >= reward_schedule.1 {
+        return Err(StdError::generic_err(
+            "End schedule block has to be greater than beginning block",
+        ));
+    }
+
+    if reward_schedule.2 == Uint128::from(0u128) {
+        return Err(StdError::generic_err("Reward has to be greater than 0"));
+    }
+
+    let curve = curve.unwrap_or_default();
+    validate_curve(&curve)?;
+
+    //we finally use that instead of max existing block check on schedule, so we avoid having to migrate the contract if a mistake was done in putting a period too far away
+    // the archived schedules ended before the current block, the new one cannot overlap them
+    // the rolling period is rolled over by `NotifyRewardAmount`, it can overlap fixed schedules
+    for schedule in read_reward_schedules(deps.storage)?
+        .iter()
+        .filter(|s| Some(s.id) != config.rolling_schedule_id)
+    {
+        if reward_schedule.0 >= schedule.start && reward_schedule.0 <= schedule.end
+            || reward_schedule.1 >= schedule.start && reward_schedule.1 <= schedule.end
+        {
+            return Err(StdError::generic_err(
+                "The new reward schedule has to be a new period, the period is overtaking an existing upcoming schedule period",
+            ));
+        }
+    }
+
+    let schedule_id = config.next_schedule_id;
+    config.next_schedule_id += 1;
+    store_reward_schedule(
+        deps.storage,
+        &RewardSchedule {
+            id: schedule_id,
+            start: reward_schedule.0,
+            end: reward_schedule.1,
+            amount: reward_schedule.2,
+            label: label.clone(),
+            curve,
+        },
+    )?;
+
+    // update config
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&AddRewardResponse { schedule_id })?)
+        .add_attributes(vec![
+            ("action", "add_reward_schedule"),
+            ("schedule_id", &schedule_id.to_string()),
+            ("starting_block", &reward_schedule.0.to_string()),
+            ("end_block", &reward_schedule.1.to_string()),
+            ("total_emission", &reward_schedule.2.to_string()),
+        ])
+        .add_event(
+            AddRewardScheduleEvent {
+                schedule_id,
+                start_block: reward_schedule.0,
+                end_block: reward_schedule.1,
+                amount: reward_schedule.2,
+                label,
+            }
+            .to_event(),
+        ))
+}
+
+// the amount was sent along by the owner, so the rolling periods are always funded
+pub fn notify_reward_amount(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(&sender)?;
+    let mut state: State = read_state(deps.storage)?;
+
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut config: Config = read_config(deps.storage)?;
+    if config.rewards_duration == 0 {
+        return Err(StdError::generic_err("Rewards duration is not set"));
+    }
+
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Reward has to be greater than 0"));
+    }
+
+    let block_height = env.block.height;
+    compute_reward(deps.storage, &mut state, block_height)?;
+
+    // cut the current period at this block, what it did not emit rolls over
+    let mut leftover = Uint128::zero();
+    if let Some(schedule_id) = config.rolling_schedule_id {
+        if config.period_finish > block_height {
+            let mut schedule = read_reward_schedules(deps.storage)?
+                .into_iter()
+                .find(|s| s.id == schedule_id)
+                .ok_or_else(|| StdError::generic_err("Reward schedule not found"))?;
+
+            let emitted = emitted_amount(&schedule, schedule.start, block_height);
+            leftover = schedule.amount - emitted;
+            if emitted.is_zero() {
+                remove_reward_schedule(deps.storage, &schedule);
+            } else {
+                schedule.end = block_height;
+                schedule.amount = emitted;
+                store_reward_schedule(deps.storage, &schedule)?;
+            }
+        }
+    }
+
+    let schedule_id = config.next_schedule_id;
+    let period_finish = block_height + config.rewards_duration;
+    store_reward_schedule(
+        deps.storage,
+        &RewardSchedule {
+            id: schedule_id,
+            start: block_height,
+            end: period_finish,
+            amount: amount + leftover,
+            label: None,
+            curve: EmissionCurve::Linear,
+        },
+    )?;
+
+    config.next_schedule_id += 1;
+    config.period_finish = period_finish;
+    config.rolling_schedule_id = Some(schedule_id);
+    store_config(deps.storage, &config)?;
+
+    // archives the cut period
+    store_state(deps.storage, &state, env.block.height)?;
+
+    Ok(Response::new()
+        .set_data(to_binary(&AddRewardResponse { schedule_id })?)
+        .add_messages(reward_funding_msgs(deps.as_ref(), &config, amount)?)
+        .add_attributes(vec![
+            ("action", "notify_reward_amount"),
+            ("schedule_id", &schedule_id.to_string()),
+            ("amount", &amount.to_string()),
+            ("leftover", &leftover.to_string()),
+            ("period_finish", &period_finish.to_string()),
+        ])
+        .add_event(
+            NotifyRewardAmountEvent {
+                schedule_id,
+                amount,
+                leftover,
+                period_finish,
+            }
+            .to_event(),
+        ))
+}
+
+pub fn set_rewards_duration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rewards_duration: u64,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let state: State = read_state(deps.storage)?;
+
+    if sender_addr_raw != state.owner_address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if rewards_duration == 0 {
+        return Err(StdError::generic_err(
+            "Rewards duration has to be greater than 0",
+        ));
+    }
+
+    let mut config: Config = read_config(deps.storage)?;
+    if config.period_finish > env.block.height {
+        return Err(StdError::generic_err(
+            "Previous rewards period must be complete before changing the duration for the new period",
+        ));
+    }
+
+    config.rewards_duration = rewards_duration;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "set_rewards_duration"),
+            ("rewards_duration", &rewards_duration.to_string()),
+        ])
+        .add_event(SetRewardsDurationEvent { rewards_duration }.to_event()))
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Bond { pool_id, staker }) => {
+            let pool_id = pool_id.unwrap_or(DEFAULT_POOL_ID);
+            let pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+
+            // only staking token contract can execute this message
+            let sender_token = AssetInfoRaw::Token {
+                contract_addr: deps.api.addr_canonicalize(info.sender.as_str())?,
+            };
+            if pool_info.staking_token != sender_token {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            // only the trusted forwarders bond on behalf of the staker
+            let staker = match staker {
+                Some(staker) => {
+                    if !is_trusted_forwarder(deps.as_ref(), &env, &cw20_msg.sender)? {
+                        return Err(StdError::generic_err("unauthorized"));
+                    }
+
+                    deps.api.addr_validate(&staker)?
+                }
+                None => deps.api.addr_validate(&cw20_msg.sender)?,
+            };
+            bond(deps, env, pool_id, staker, cw20_msg.amount)
+        }
+        Ok(Cw20HookMsg::DistributeNow { pool_id }) => {
+            // only the reward token can be distributed at once
+            let config: Config = read_config(deps.storage)?;
+            if config.xdefi_token != deps.api.addr_canonicalize(info.sender.as_str())? {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            distribute_now(
+                deps,
+                env,
+                pool_id.unwrap_or(DEFAULT_POOL_ID),
+                cw20_msg.amount,
+            )
+        }
+        Ok(Cw20HookMsg::NotifyRewardAmount {}) => {
+            // only the reward token funds the rolling periods
+            let config: Config = read_config(deps.storage)?;
+            if config.xdefi_token != deps.api.addr_canonicalize(info.sender.as_str())? {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            notify_reward_amount(deps, env, cw20_msg.sender, cw20_msg.amount)
+        }
+        Err(_) => Err(StdError::generic_err("data should be given")),
+    }
+}
+
+pub fn bond_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    staker: Option<String>,
+) -> StdResult<Response> {
+    let pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+
+    let denom = match pool_info.staking_token {
+        AssetInfoRaw::NativeToken { denom } => denom,
+        AssetInfoRaw::Token { .. } => {
+            return Err(StdError::generic_err("staking token is not a native coin"))
+        }
+    };
+
+    // only the staking denom can be attached, and nothing else
+    if info.funds.len() != 1 || info.funds[0].denom != denom {
+        return Err(StdError::generic_err(format!(
+            "only {} can be bonded",
+            denom
+        )));
+    }
+
+    let amount = info.funds[0].amount;
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "Bond amount has to be greater than 0",
+        ));
+    }
+
+    // only the trusted forwarders bond on behalf of the staker
+    let staker = match staker {
+        Some(staker) => {
+            if !is_trusted_forwarder(deps.as_ref(), &env, info.sender.as_str())? {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            deps.api.addr_validate(&staker)?
+        }
+        None => info.sender,
+    };
+
+    bond(deps, env, pool_id, staker, amount)
+}
+
+pub fn bond(
+    deps: DepsMut,
+    env: Env,
+    pool_id: u64,
+    sender_addr: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(sender_addr.as_str())?;
+
+    let config: Config = read_config(deps.storage)?;
+    if config.allowlist_enabled && !is_allowlisted(deps.storage, &sender_addr_raw)? {
+        return Err(StdError::generic_err("sender is not allowlisted"));
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, pool_id, &sender_addr_raw)?;
+
+    // Compute global reward, pool reward & staker reward
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+    compute_staker_reward(&pool_info, &mut staker_info)?;
+
+    // Increase bond_amount
+    let old_bond = staker_info.bond_amount;
+    increase_bond_amount(&mut pool_info, &mut staker_info, amount)?;
+    if is_reward_token(&config, &pool_info.staking_token) {
+        state.total_bonded_reward_token = state.total_bonded_reward_token.checked_add(amount)?;
+    }
+
+    // revenue deferred while nothing was bonded goes to the stakers from now on
+    apply_undistributed_revenue(&mut pool_info);
+
+    // Store updated state with staker's staker_info
+    store_staker_info(
+        deps.storage,
+        pool_id,
+        &sender_addr_raw,
+        &staker_info,
+        env.block.height,
+    )?;
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+    store_state(deps.storage, &state, env.block.height)?;
+
+    let hook_msgs = stake_changed_hook_msgs(
+        deps.as_ref(),
+        pool_id,
+        &sender_addr,
+        old_bond,
+        staker_info.bond_amount,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(hook_msgs)
+        .add_attributes(vec![
+            ("action", "bond"),
+            ("pool_id", &pool_id.to_string()),
+            ("owner", sender_addr.as_str()),
+            ("amount", amount.to_string().as_str()),
+        ])
+        .add_event(
+            BondEvent {
+                pool_id,
+                staker: sender_addr.to_string(),
+                amount,
+                bond_amount: staker_info.bond_amount,
+                total_bond_amount: pool_info.total_bond_amount,
+                global_reward_index: pool_info.global_reward_index,
+            }
+            .to_event(),
+        ))
+}
+
+// distribute xdefi to the pool stakers at once, by raising the pool reward index
+pub fn distribute_now(
+    deps: DepsMut,
+    env: Env,
+    pool_id: u64,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+
+    // nobody could claim it, so keep it for the next bond
+    let deferred = pool_info.total_bond_amount.is_zero();
+    let credited_amount = if deferred {
+        pool_info.undistributed_revenue = pool_info.undistributed_revenue.checked_add(amount)?;
+        amount
+    } else {
+        let increase = Decimal::from_ratio(amount, pool_info.total_bond_amount);
+        pool_info.global_reward_index = pool_info.global_reward_index + increase;
+        pool_info.total_bond_amount * increase
+    };
+    state.total_distributed = state.total_distributed.checked_add(credited_amount)?;
+
+    // the rounding remainder stays owed along with the rewards not credited yet
+    state.uncredited_rewards = state
+        .uncredited_rewards
+        .checked_add(amount.checked_sub(credited_amount)?)?;
+
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+    store_state(deps.storage, &state, env.block.height)?;
+
+    Ok(Response::new()
+        .add_messages(reward_funding_msgs(deps.as_ref(), &config, amount)?)
+        .add_attributes(vec![
+            ("action", "distribute_now"),
+            ("pool_id", &pool_id.to_string()),
+            ("amount", &amount.to_string()),
+            ("deferred", &deferred.to_string()),
+        ])
+        .add_event(
+            DistributeNowEvent {
+                pool_id,
+                asset: deps.api.addr_humanize(&config.xdefi_token)?.to_string(),
+                amount,
+                deferred,
+            }
+            .to_event(),
+        ))
+}
+
+// rewards are paid out of the distributor when set, so the received ones go there
+fn reward_funding_msgs(deps: Deps, config: &Config, amount: Uint128) -> StdResult<Vec<CosmosMsg>> {
+    Ok(match &config.distributor_contract {
+        Some(distributor) => vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps.api.addr_humanize(&config.xdefi_token)?.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: deps.api.addr_humanize(distributor)?.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })],
+        None => vec![],
+    })
+}
+
+// distribute the attached native coins to the pool stakers at once
+pub fn distribute_native_now(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+) -> StdResult<Response> {
+    if info.funds.is_empty() || info.funds.iter().any(|coin| coin.amount.is_zero()) {
+        return Err(StdError::generic_err("No coins to distribute"));
+    }
+
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+
+    let deferred = pool_info.total_bond_amount.is_zero();
+    let mut events: Vec<Event> = vec![];
+    for coin in info.funds {
+        events.push(
+            DistributeNowEvent {
+                pool_id,
+                asset: coin.denom.to_string(),
+                amount: coin.amount,
+                deferred,
+            }
+            .to_event(),
+        );
+
+        if deferred {
+            add_coin(&mut pool_info.undistributed_native_revenue, coin);
+        } else {
+            increase_native_reward_index(&mut pool_info, &coin);
+        }
+    }
+
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "distribute_native_now"),
+            ("pool_id", &pool_id.to_string()),
+            ("deferred", &deferred.to_string()),
+        ])
+        .add_events(events))
+}
+
+pub fn unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    amount: Uint128,
+    claim_rewards: bool,
+    send_to: Option<(Addr, Binary)>,
+) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_not_frozen(deps.as_ref(), &sender_addr_raw)?;
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, pool_id, &sender_addr_raw)?;
+
+    if staker_info.bond_amount < amount {
+        return Err(StdError::generic_err("Cannot unbond more than bond amount"));
+    }
+
+    let native = matches!(pool_info.staking_token, AssetInfoRaw::NativeToken { .. });
+    let send_to = match send_to {
+        Some((contract, msg)) => Some((contract, staker_hook_msg(native, &info.sender, &msg)?)),
+        None => None,
+    };
+
+    // Compute global reward, pool reward & staker reward
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+    compute_staker_reward(&pool_info, &mut staker_info)?;
+
+    // Decrease bond_amount
+    let old_bond = staker_info.bond_amount;
+    decrease_bond_amount(&mut pool_info, &mut staker_info, amount)?;
+    if is_reward_token(&config, &pool_info.staking_token) {
+        state.total_bonded_reward_token = state.total_bonded_reward_token.checked_sub(amount)?;
+    }
+
+    let action = if send_to.is_some() {
+        "unbond_to"
+    } else {
+        "unbond"
+    };
+    let mut attributes = vec![
+        attr("action", action),
+        attr("pool_id", pool_id.to_string()),
+        attr("owner", info.sender.as_str()),
+        attr("amount", amount.to_string()),
+    ];
+    if let Some((contract, _)) = &send_to {
+        attributes.push(attr("contract", contract.as_str()));
+    }
+
+    let mut event = UnbondEvent {
+        pool_id,
+        staker: info.sender.to_string(),
+        amount,
+        reward_amount: Uint128::zero(),
+        bond_amount: staker_info.bond_amount,
+        total_bond_amount: pool_info.total_bond_amount,
+        global_reward_index: pool_info.global_reward_index,
+        contract: send_to.as_ref().map(|(contract, _)| contract.to_string()),
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![staking_token_payout_msg(
+        deps.api,
+        &pool_info.staking_token,
+        &info.sender,
+        amount,
+        send_to,
+    )?];
+    messages.extend(stake_changed_hook_msgs(
+        deps.as_ref(),
+        pool_id,
+        &info.sender,
+        old_bond,
+        staker_info.bond_amount,
+    )?);
+
+    // Withdraw pending rewards along with the unbonded tokens
+    let mut events: Vec<Event> = vec![];
+    let mut reward_amount = Uint128::zero();
+    if claim_rewards {
+        events.extend(check_reward_solvency(
+            deps.as_ref(),
+            &env,
+            &config,
+            &mut state,
+        )?);
+        reward_amount = staker_info.pending_reward;
+        staker_info.pending_reward = Uint128::zero();
+        record_claim(
+            deps.storage,
+            &mut state,
+            pool_id,
+            &sender_addr_raw,
+            reward_amount,
+        )?;
+        messages.extend(native_rewards_payout_msg(&mut staker_info, &info.sender));
+
+        if !reward_amount.is_zero() {
+            let (reward_msgs, reward_events) = payout_rewards(
+                deps.storage,
+                deps.api,
+                &config,
+                &mut state,
+                &info.sender,
+                reward_amount,
+                None,
+                &env,
+            )?;
+            messages.extend(reward_msgs);
+            events.extend(reward_events);
+        }
+    }
+
+    // Store or remove updated rewards info
+    // depends on the left pending reward and bond amount
+    if staker_info.pending_reward.is_zero()
+        && staker_info.pending_native_rewards.is_empty()
+        && staker_info.bond_amount.is_zero()
+    {
+        remove_staker_info(deps.storage, pool_id, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            pool_id,
+            &sender_addr_raw,
+            &staker_info,
+            env.block.height,
+        )?;
+    }
+
+    // Store updated state
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+    store_state(deps.storage, &state, env.block.height)?;
+
+    attributes.push(attr("reward_amount", reward_amount.to_string()));
+    event.reward_amount = reward_amount;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(attributes)
+        .add_event(event.to_event())
+        .add_events(events))
+}
+
+// unbond the whole bond amount and withdraw rewards to executor
+pub fn exit(deps: DepsMut, env: Env, info: MessageInfo, pool_id: u64) -> StdResult<Response> {
+    let config: Config = read_config(deps.storage)?;
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_not_frozen(deps.as_ref(), &sender_addr_raw)?;
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, pool_id, &sender_addr_raw)?;
+
+    // Compute global reward, pool reward & staker reward
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+    compute_staker_reward(&pool_info, &mut staker_info)?;
+
+    let amount = staker_info.bond_amount;
+    let reward_amount = staker_info.pending_reward;
+    if amount.is_zero() && reward_amount.is_zero() && staker_info.pending_native_rewards.is_empty()
+    {
+        return Err(StdError::generic_err("Nothing to exit"));
+    }
+
+    let mut events: Vec<Event> = check_reward_solvency(deps.as_ref(), &env, &config, &mut state)?
+        .into_iter()
+        .collect();
+
+    // Decrease bond_amount to zero and remove staker info
+    decrease_bond_amount(&mut pool_info, &mut staker_info, amount)?;
+    if is_reward_token(&config, &pool_info.staking_token) {
+        state.total_bonded_reward_token = state.total_bonded_reward_token.checked_sub(amount)?;
+    }
+    remove_staker_info(deps.storage, pool_id, &sender_addr_raw, env.block.height)?;
+    record_claim(
+        deps.storage,
+        &mut state,
+        pool_id,
+        &sender_addr_raw,
+        reward_amount,
+    )?;
+
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !amount.is_zero() {
+        messages.push(staking_token_payout_msg(
+            deps.api,
+            &pool_info.staking_token,
+            &info.sender,
+            amount,
+            None,
+        )?);
+    }
+
+    messages.extend(native_rewards_payout_msg(&mut staker_info, &info.sender));
+
+    if !reward_amount.is_zero() {
+        let (reward_msgs, reward_events) = payout_rewards(
+            deps.storage,
+            deps.api,
+            &config,
+            &mut state,
+            &info.sender,
+            reward_amount,
+            None,
+            &env,
+        )?;
+        messages.extend(reward_msgs);
+        events.extend(reward_events);
+    }
+
+    // Store updated state, along with the vested rewards
+    store_state(deps.storage, &state, env.block.height)?;
+
+    if !amount.is_zero() {
+        messages.extend(stake_changed_hook_msgs(
+            deps.as_ref(),
+            pool_id,
+            &info.sender,
+            amount,
+            Uint128::zero(),
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "exit"),
+            ("pool_id", &pool_id.to_string()),
+            ("owner", info.sender.as_str()),
+            ("amount", amount.to_string().as_str()),
+            ("reward_amount", reward_amount.to_string().as_str()),
+        ])
+        .add_event(
+            ExitEvent {
+                pool_id,
+                staker: info.sender.to_string(),
+                amount,
+                reward_amount,
+                total_bond_amount: pool_info.total_bond_amount,
+                global_reward_index: pool_info.global_reward_index,
+            }
+            .to_event(),
+        )
+        .add_events(events))
+}
+
+// withdraw rewards to executor, or send them to a contract along with a hook message
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    send_to: Option<(Addr, Binary)>,
+) -> StdResult<Response> {
+    let sender_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    assert_not_frozen(deps.as_ref(), &sender_addr_raw)?;
+
+    let config: Config = read_config(deps.storage)?;
+    if send_to.is_some() && config.reward_vesting_period.is_some() {
+        return Err(StdError::generic_err(
+            "vesting rewards cannot be sent to a contract",
+        ));
+    }
+
+    let send_to = match send_to {
+        Some((contract, msg)) => Some((contract, staker_hook_msg(false, &info.sender, &msg)?)),
+        None => None,
+    };
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    let mut staker_info = read_staker_info(deps.storage, pool_id, &sender_addr_raw)?;
+
+    // Compute global reward, pool reward & staker reward
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+    compute_pool_reward(&mut state, &mut pool_info);
+    compute_staker_reward(&pool_info, &mut staker_info)?;
+
+    let alert = check_reward_solvency(deps.as_ref(), &env, &config, &mut state)?;
+
+    let amount = staker_info.pending_reward;
+    staker_info.pending_reward = Uint128::zero();
+    let native_rewards_msg = native_rewards_payout_msg(&mut staker_info, &info.sender);
+    record_claim(deps.storage, &mut state, pool_id, &sender_addr_raw, amount)?;
+
+    // cw20 send rejects zero amounts, so fail early with a clear error
+    if send_to.is_some() && amount.is_zero() {
+        return Err(StdError::generic_err("No rewards to withdraw"));
+    }
+
+    // Store or remove updated rewards info
+    // depends on the left pending reward and bond amount
+    if staker_info.bond_amount.is_zero() {
+        remove_staker_info(deps.storage, pool_id, &sender_addr_raw, env.block.height)?;
+    } else {
+        store_staker_info(
+            deps.storage,
+            pool_id,
+            &sender_addr_raw,
+            &staker_info,
+            env.block.height,
+        )?;
+    }
+
+    store_pool_info(deps.storage, pool_id, &pool_info, env.block.height)?;
+
+    let action = if send_to.is_some() {
+        "withdraw_to"
+    } else {
+        "withdraw"
+    };
+    let mut attributes = vec![
+        attr("action", action),
+        attr("pool_id", pool_id.to_string()),
+        attr("owner", info.sender.as_str()),
+        attr("amount", amount.to_string()),
+    ];
+    if let Some((contract, _)) = &send_to {
+        attributes.push(attr("contract", contract.as_str()));
+    }
+
+    let event = WithdrawEvent {
+        pool_id,
+        staker: info.sender.to_string(),
+        amount,
+        global_reward_index: pool_info.global_reward_index,
+        contract: send_to.as_ref().map(|(contract, _)| contract.to_string()),
+    };
+
+    let (reward_msgs, reward_events) = payout_rewards(
+        deps.storage,
+        deps.api,
+        &config,
+        &mut state,
+        &info.sender,
+        amount,
+        send_to,
+        &env,
+    )?;
+
+    // Store updated state, along with the vested rewards
+    store_state(deps.storage, &state, env.block.height)?;
+
+    Ok(Response::new()
+        .add_messages(reward_msgs)
+        .add_messages(native_rewards_msg)
+        .add_attributes(attributes)
+        .add_event(event.to_event())
+        .add_events(alert)
+        .add_events(reward_events))
+}
+
+pub fn change_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut state: State = read_state(deps.storage)?;
+
+    // get gov address by querying owner address
+    let gov_addr_raw: CanonicalAddr = state.owner_address;
+    if sender_addr_raw != gov_addr_raw {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    state.owner_address = deps.api.addr_canonicalize(&new_owner)?;
+    // update state with new owner address
+    store_state(deps.storage, &state, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_owner")
+        .add_attribute("new_owner", new_owner.to_string())
+        .add_event(ChangeOwnerEvent { new_owner }.to_event()))
+}
+
+pub fn migrate_staking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_staking_contract: String,
+) -> StdResult<Response> {
+    let sender_addr_raw: CanonicalAddr = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    let anc_token: Addr = deps.api.addr_humanize(&config.xdefi_token)?;
+
+    // get gov address by querying owner address
+    let gov_addr_raw: CanonicalAddr = state.owner_address.clone();
+    if sender_addr_raw != gov_addr_raw {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // compute global reward, sets last_distributed_height to env.block.height
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+
+    // the rewards scheduled while halted were never emitted, they are not distributed
+    let block_height = if state.emission_halted {
+        state.halted_since
+    } else {
+        env.block.height
+    };
+
+    // the archived schedules ended, only the ones ended while halted may be partially
+    // distributed; reading them all is fine once
+    let archived = read_archived_reward_schedules(deps.storage)?;
+    let archived_amount: Uint128 = archived
+        .iter()
+        .map(|s| emitted_amount(s, s.start, block_height))
+        .sum();
+    let mut schedules = read_reward_schedules(deps.storage)?;
+    let total_distribution_amount: Uint128 = archived.iter().map(|s| s.amount).sum::<Uint128>()
+        + schedules.iter().map(|item| item.amount).sum::<Uint128>();
+
     // eliminate distribution slots that have not started
-    config
-        .distribution_schedule
-        .retain(|slot| slot.0 < block_height);
+    for slot in schedules.iter().filter(|slot| slot.start >= block_height) {
+        remove_reward_schedule(deps.storage, slot);
+    }
+    schedules.retain(|slot| slot.start < block_height);
+
+    let mut distributed_amount = archived_amount;
+    for s in schedules.iter_mut() {
+        if s.end < block_height {
+            // all distributed
+            distributed_amount += s.amount;
+        } else {
+            // partially distributed slot
+            let distributed_amount_on_slot = emitted_amount(s, s.start, block_height);
+            distributed_amount += distributed_amount_on_slot;
+
+            // modify distribution slot, the curve has no meaning anymore once cut
+            s.end = block_height;
+            s.amount = distributed_amount_on_slot;
+            s.curve = EmissionCurve::Linear;
+            store_reward_schedule(deps.storage, s)?;
+        }
+    }
+
+    // the rolling period is cut or removed with the other slots
+    if config.period_finish > block_height {
+        config.period_finish = block_height;
+        store_config(deps.storage, &config)?;
+    }
+
+    // nothing is left to emit on resume
+    state.halted_since = state.last_distributed;
+
+    // update state, archives the distributed slots
+    store_state(deps.storage, &state, env.block.height)?;
+
+    let remaining_anc = total_distribution_amount.checked_sub(distributed_amount)?;
+    let event = MigrateStakingEvent {
+        new_staking_contract: new_staking_contract.clone(),
+        distributed_amount,
+        remaining_amount: remaining_anc,
+    };
+
+    // rewards paid by the distributor never left it, there is nothing to send
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if config.distributor_contract.is_none() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anc_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: new_staking_contract.clone(),
+                amount: remaining_anc,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    for hook in read_hooks(deps.storage)? {
+        messages.push(
+            StakeChangedHookMsg::StakingMigrated {
+                new_staking_contract: new_staking_contract.clone(),
+            }
+            .into_cosmos_msg(deps.api.addr_humanize(&hook)?.to_string())?,
+        );
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "migrate_staking"),
+            ("distributed_amount", &distributed_amount.to_string()),
+            ("remaining_amount", &remaining_anc.to_string()),
+        ])
+        .add_event(event.to_event()))
+}
+
+// account the rewards claimed by the staker, in total and per staker
+fn record_claim(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    pool_id: u64,
+    staker: &CanonicalAddr,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    state.total_claimed = state.total_claimed.checked_add(amount)?;
+    increase_staker_claimed(storage, pool_id, staker, amount)
+}
+
+// halt the schedule emission once the rewards owed exceed the reward balance,
+// so the withdrawals keep paying out what is left instead of failing;
+// a withdrawal lowers both sides by the same amount, so it is checked beforehand
+fn check_reward_solvency(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    state: &mut State,
+) -> StdResult<Option<Event>> {
+    if state.emission_halted {
+        return Ok(None);
+    }
+
+    let (reward_balance, pending_rewards, unemitted_rewards) =
+        compute_reward_solvency(deps, env, config, state)?;
+    let reward_liability = pending_rewards.checked_add(unemitted_rewards)?;
+    if reward_liability <= reward_balance {
+        return Ok(None);
+    }
+
+    state.emission_halted = true;
+    state.halted_since = state.last_distributed;
+    Ok(Some(
+        InsolvencyAlertEvent {
+            reward_balance,
+            reward_liability,
+            shortfall: reward_liability - reward_balance,
+        }
+        .to_event(),
+    ))
+}
+
+// balances of the staking and reward tokens against the bonded amounts and the rewards owed;
+// the state must be computed up to the current block
+fn compute_solvency(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    state: &State,
+) -> StdResult<SolvencyResponse> {
+    // sum up the bonded amounts by staking token, over all the pools
+    let mut bonded: Vec<(AssetInfoRaw, Uint128)> = vec![];
+    for pool_id in 0..state.pool_count {
+        let pool_info = read_pool_info(deps.storage, pool_id)?;
+        match bonded
+            .iter_mut()
+            .find(|(token, _)| *token == pool_info.staking_token)
+        {
+            Some((_, amount)) => *amount += pool_info.total_bond_amount,
+            None => bonded.push((pool_info.staking_token, pool_info.total_bond_amount)),
+        }
+    }
+
+    let mut staking_tokens: Vec<StakingTokenSolvency> = vec![];
+    for (token, liability) in bonded {
+        let balance = query_asset_balance(deps, &token, &env.contract.address)?;
+        staking_tokens.push(StakingTokenSolvency {
+            staking_token: token.to_normal(deps.api)?,
+            balance,
+            liability,
+            shortfall: liability.saturating_sub(balance),
+        });
+    }
+
+    let (reward_balance, pending_rewards, unemitted_rewards) =
+        compute_reward_solvency(deps, env, config, state)?;
+    let reward_liability = pending_rewards.checked_add(unemitted_rewards)?;
+
+    Ok(SolvencyResponse {
+        staking_tokens,
+        reward_balance,
+        pending_rewards,
+        unemitted_rewards,
+        reward_liability,
+        reward_shortfall: reward_liability.saturating_sub(reward_balance),
+        emission_halted: state.emission_halted,
+    })
+}
+
+// reward balance of the payer, rewards owed and rewards left to emit, without reading
+// the pools, so it is cheap enough to check on every withdrawal;
+// the state must be computed up to the current block
+fn compute_reward_solvency(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    state: &State,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    let reward_token = AssetInfoRaw::Token {
+        contract_addr: config.xdefi_token.clone(),
+    };
+
+    // rewards bonded as a staking token are owed to their stakers, not to the reward liability
+    let reward_balance = match &config.distributor_contract {
+        Some(distributor) => {
+            let distributor = deps.api.addr_humanize(distributor)?;
+            query_asset_balance(deps, &reward_token, &distributor)?
+        }
+        None => query_asset_balance(deps, &reward_token, &env.contract.address)?
+            .saturating_sub(state.total_bonded_reward_token),
+    };
+
+    // the pools not synced yet may still credit their share of the emitted rewards
+    let pending_rewards = state
+        .total_distributed
+        .saturating_sub(state.total_claimed)
+        .checked_add(state.total_vesting)?
+        .checked_add(state.uncredited_rewards)?;
+
+    // the rewards scheduled while halted are still owed, they are emitted on resume
+    let emitted_until = if state.emission_halted {
+        state.halted_since
+    } else {
+        state.last_distributed
+    };
+    let unemitted_rewards = compute_unemitted_rewards(
+        &read_reward_schedules_between(deps.storage, emitted_until, u64::MAX)?,
+        emitted_until,
+    );
+
+    Ok((reward_balance, pending_rewards, unemitted_rewards))
+}
+
+// rewards of the distribution schedules left to emit after the given block height
+fn compute_unemitted_rewards(schedules: &[RewardSchedule], last_distributed: u64) -> Uint128 {
+    schedules
+        .iter()
+        .map(|s| s.amount - emitted_amount(s, s.start, last_distributed))
+        .sum()
+}
+
+fn query_asset_balance(deps: Deps, asset: &AssetInfoRaw, account: &Addr) -> StdResult<Uint128> {
+    match asset {
+        AssetInfoRaw::NativeToken { denom } => {
+            Ok(deps.querier.query_balance(account, denom)?.amount)
+        }
+        AssetInfoRaw::Token { contract_addr } => {
+            let res: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+                deps.api.addr_humanize(contract_addr)?,
+                &Cw20QueryMsg::Balance {
+                    address: account.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+    }
+}
+
+// pay out claimed rewards to the staker, or to a contract along with a hook message;
+// with reward vesting enabled they are locked in the staker vesting ledger instead
+#[allow(clippy::too_many_arguments)]
+fn payout_rewards(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    config: &Config,
+    state: &mut State,
+    staker: &Addr,
+    amount: Uint128,
+    send_to: Option<(Addr, Binary)>,
+    env: &Env,
+) -> StdResult<(Vec<CosmosMsg>, Vec<Event>)> {
+    let period = match config.reward_vesting_period {
+        Some(period) => period,
+        None => {
+            return Ok((
+                reward_payout_msgs(api, env, config, staker, amount, send_to)?,
+                vec![],
+            ))
+        }
+    };
+    let block_height = env.block.height;
+
+    if send_to.is_some() {
+        return Err(StdError::generic_err(
+            "vesting rewards cannot be sent to a contract",
+        ));
+    }
+
+    if amount.is_zero() {
+        return Ok((vec![], vec![]));
+    }
+
+    let staker_raw = api.addr_canonicalize(staker.as_str())?;
+    let mut vesting_info = read_reward_vesting(storage, &staker_raw)?.unwrap_or(VestingInfo {
+        schedules: vec![],
+        last_claim_time: block_height,
+        cliff: None,
+        revocable: false,
+    });
+
+    let end_block = block_height + period;
+    vesting_info
+        .schedules
+        .push((block_height, end_block, amount));
+    store_reward_vesting(storage, &staker_raw, &vesting_info)?;
+    state.total_vesting = state.total_vesting.checked_add(amount)?;
+
+    Ok((
+        vec![],
+        vec![VestRewardsEvent {
+            staker: staker.to_string(),
+            amount,
+            start_block: block_height,
+            end_block,
+        }
+        .to_event()],
+    ))
+}
+
+// transfer rewards out of the contract balance, or request them from the distributor
+fn reward_payout_msgs(
+    api: &dyn Api,
+    env: &Env,
+    config: &Config,
+    recipient: &Addr,
+    amount: Uint128,
+    send_to: Option<(Addr, Binary)>,
+) -> StdResult<Vec<CosmosMsg>> {
+    // cw20 rejects zero amounts, the staker may only have native rewards to withdraw
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let token = api.addr_humanize(&config.xdefi_token)?;
+    let distributor = match &config.distributor_contract {
+        Some(distributor) => api.addr_humanize(distributor)?,
+        None => return Ok(vec![cw20_payout_msg(token, recipient, amount, send_to)?]),
+    };
+
+    // spend does not take a hook message, so the rewards
+    // pass through this contract on their way to a contract
+    let spend_recipient = match &send_to {
+        Some(_) => env.contract.address.clone(),
+        None => recipient.clone(),
+    };
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: distributor.to_string(),
+        msg: to_binary(&DistributorExecuteMsg::Spend {
+            recipient: spend_recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })];
+
+    if send_to.is_some() {
+        messages.push(cw20_payout_msg(token, recipient, amount, send_to)?);
+    }
 
-    let mut distributed_amount = Uint128::zero();
-    for s in config.distribution_schedule.iter_mut() {
-        if s.1 < block_height {
-            // all distributed
-            distributed_amount += s.2;
-        } else {
-            // partially distributed slot
-            let num_blocks = s.1 - s.0;
-            let distribution_amount_per_block: Decimal = Decimal::from_ratio(s.2, num_blocks);
+    Ok(messages)
+}
 
-            let passed_blocks = block_height - s.0;
-            let distributed_amount_on_slot =
-                distribution_amount_per_block * Uint128::from(passed_blocks as u128);
-            distributed_amount += distributed_amount_on_slot;
+// amount released by the vesting schedules since the last claim
+fn compute_vested_amount(vesting_info: &VestingInfo, block_height: u64) -> Uint128 {
+    released_amount(vesting_info, block_height)
+        - released_amount(vesting_info, vesting_info.last_claim_time)
+}
+
+// amount released by the vesting schedules up to the given block height
+fn released_amount(vesting_info: &VestingInfo, block_height: u64) -> Uint128 {
+    vesting_info
+        .schedules
+        .iter()
+        .map(|s| {
+            if block_height >= s.1 {
+                s.2
+            } else if block_height <= s.0 {
+                Uint128::zero()
+            } else {
+                s.2.multiply_ratio(block_height - s.0, s.1 - s.0)
+            }
+        })
+        .sum()
+}
+
+// notify every registered hook contract of a staker bond change
+fn stake_changed_hook_msgs(
+    deps: Deps,
+    pool_id: u64,
+    addr: &Addr,
+    old_bond: Uint128,
+    new_bond: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    read_hooks(deps.storage)?
+        .iter()
+        .map(|hook| {
+            StakeChangedHookMsg::StakeChangedHook {
+                pool_id,
+                addr: addr.to_string(),
+                old_bond,
+                new_bond,
+            }
+            .into_cosmos_msg(deps.api.addr_humanize(hook)?.to_string())
+        })
+        .collect()
+}
+
+// frozen stakers can neither withdraw rewards nor unbond
+fn assert_not_frozen(deps: Deps, staker: &CanonicalAddr) -> StdResult<()> {
+    if is_frozen(deps.storage, staker)? {
+        return Err(StdError::generic_err("staker is frozen"));
+    }
+
+    Ok(())
+}
+
+// the hook message sent along the tokens of the staker has to credit the staker,
+// the receiving contract would otherwise credit this contract as the token sender
+fn staker_hook_msg(native: bool, staker: &Addr, msg: &Binary) -> StdResult<Binary> {
+    let staker = staker.to_string();
+    if native {
+        if let Ok(ExecuteMsg::BondNative { pool_id, .. }) = from_binary(msg) {
+            return to_binary(&ExecuteMsg::BondNative {
+                pool_id,
+                staker: Some(staker),
+            });
+        }
+    } else {
+        if let Ok(Cw20HookMsg::Bond { pool_id, .. }) = from_binary(msg) {
+            return to_binary(&Cw20HookMsg::Bond {
+                pool_id,
+                staker: Some(staker),
+            });
+        }
 
-            // modify distribution slot
-            s.1 = block_height;
-            s.2 = distributed_amount_on_slot;
+        if let Ok(
+            GovCw20HookMsg::StakeVotingTokens {} | GovCw20HookMsg::StakeVotingTokensFor { .. },
+        ) = from_binary(msg)
+        {
+            return to_binary(&GovCw20HookMsg::StakeVotingTokensFor { staker });
         }
     }
 
-    // update config
-    store_config(deps.storage, &config)?;
-    // update state
-    store_state(deps.storage, &state)?;
+    Err(StdError::generic_err(
+        "hook message has to bond or stake on behalf of the staker",
+    ))
+}
 
-    let remaining_anc = total_distribution_amount.checked_sub(distributed_amount)?;
+// transfer tokens to the recipient, or send them to a contract along with a hook message
+fn cw20_payout_msg(
+    token: Addr,
+    recipient: &Addr,
+    amount: Uint128,
+    send_to: Option<(Addr, Binary)>,
+) -> StdResult<CosmosMsg> {
+    let msg = match send_to {
+        Some((contract, msg)) => Cw20ExecuteMsg::Send {
+            contract: contract.to_string(),
+            amount,
+            msg,
+        },
+        None => Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        },
+    };
 
-    Ok(Response::new()
-        .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: anc_token.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                recipient: new_staking_contract,
-                amount: remaining_anc,
-            })?,
-            funds: vec![],
-        })])
-        .add_attributes(vec![
-            ("action", "migrate_staking"),
-            ("distributed_amount", &distributed_amount.to_string()),
-            ("remaining_amount", &remaining_anc.to_string()),
-        ]))
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    }))
+}
+
+// pay out the native coins distributed to the staker, if any
+fn native_rewards_payout_msg(staker_info: &mut StakerInfo, recipient: &Addr) -> Option<CosmosMsg> {
+    let coins = std::mem::take(&mut staker_info.pending_native_rewards);
+    if coins.is_empty() {
+        return None;
+    }
+
+    Some(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: coins,
+    }))
+}
+
+// pay out staking tokens, as cw20 tokens or as native coins
+fn staking_token_payout_msg(
+    api: &dyn Api,
+    staking_token: &AssetInfoRaw,
+    recipient: &Addr,
+    amount: Uint128,
+    send_to: Option<(Addr, Binary)>,
+) -> StdResult<CosmosMsg> {
+    match staking_token {
+        AssetInfoRaw::Token { contract_addr } => cw20_payout_msg(
+            api.addr_humanize(contract_addr)?,
+            recipient,
+            amount,
+            send_to,
+        ),
+        AssetInfoRaw::NativeToken { denom } => {
+            let coins = vec![Coin {
+                denom: denom.to_string(),
+                amount,
+            }];
+
+            Ok(match send_to {
+                Some((contract, msg)) => CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg,
+                    funds: coins,
+                }),
+                None => CosmosMsg::Bank(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: coins,
+                }),
+            })
+        }
+    }
+}
+
+// increase bond amounts, within the pool bond limits
+fn increase_bond_amount(
+    pool_info: &mut PoolInfo,
+    staker_info: &mut StakerInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    let limits = &pool_info.bond_limits;
+    if let Some(min_bond_amount) = limits.min_bond_amount {
+        if amount < min_bond_amount {
+            return Err(StdError::generic_err(format!(
+                "Bond amount is below the minimum of {}",
+                min_bond_amount
+            )));
+        }
+    }
+
+    pool_info.total_bond_amount = pool_info.total_bond_amount.checked_add(amount)?;
+    staker_info.bond_amount = staker_info.bond_amount.checked_add(amount)?;
+
+    if let Some(max_total_bond) = limits.max_total_bond {
+        if pool_info.total_bond_amount > max_total_bond {
+            return Err(StdError::generic_err(format!(
+                "Bond exceeds the pool cap of {}",
+                max_total_bond
+            )));
+        }
+    }
+
+    if let Some(max_bond_per_staker) = limits.max_bond_per_staker {
+        if staker_info.bond_amount > max_bond_per_staker {
+            return Err(StdError::generic_err(format!(
+                "Bond exceeds the per staker cap of {}",
+                max_bond_per_staker
+            )));
+        }
+    }
+
+    Ok(())
 }
 
-fn increase_bond_amount(state: &mut State, staker_info: &mut StakerInfo, amount: Uint128) {
-    state.total_bond_amount += amount;
-    staker_info.bond_amount += amount;
+fn is_reward_token(config: &Config, staking_token: &AssetInfoRaw) -> bool {
+    matches!(staking_token, AssetInfoRaw::Token { contract_addr } if *contract_addr == config.xdefi_token)
 }
 
 fn decrease_bond_amount(
-    state: &mut State,
+    pool_info: &mut PoolInfo,
     staker_info: &mut StakerInfo,
     amount: Uint128,
 ) -> StdResult<()> {
-    state.total_bond_amount = state.total_bond_amount.checked_sub(amount)?;
+    pool_info.total_bond_amount = pool_info.total_bond_amount.checked_sub(amount)?;
     staker_info.bond_amount = staker_info.bond_amount.checked_sub(amount)?;
     Ok(())
 }
 
-// compute distributed rewards and update global reward index
-fn compute_reward(config: &Config, state: &mut State, block_height: u64) {
-    if state.total_bond_amount.is_zero() {
+// compute distributed rewards and update reward per allocation point
+fn compute_reward(storage: &dyn Storage, state: &mut State, block_height: u64) -> StdResult<()> {
+    if state.total_alloc_point == 0 || state.emission_halted {
         state.last_distributed = block_height;
-        return;
+        return Ok(());
     }
 
     let mut distributed_amount: Uint128 = Uint128::zero();
-    for s in config.distribution_schedule.iter() {
-        if s.0 > block_height || s.1 < state.last_distributed {
+    for s in read_reward_schedules_between(storage, state.last_distributed, block_height)?.iter() {
+        if s.start > block_height || s.end < state.last_distributed {
             continue;
         }
 
-        // min(s.1, block_height) - max(s.0, last_distributed)
-        let passed_blocks =
-            std::cmp::min(s.1, block_height) - std::cmp::max(s.0, state.last_distributed);
-
-        let num_blocks = s.1 - s.0;
-        let distribution_amount_per_block: Decimal = Decimal::from_ratio(s.2, num_blocks);
-        distributed_amount += distribution_amount_per_block * Uint128::from(passed_blocks as u128);
+        // emission over [max(s.start, last_distributed), min(s.end, block_height)]
+        distributed_amount += emitted_amount(s, state.last_distributed, block_height);
     }
 
     state.last_distributed = block_height;
-    state.global_reward_index = state.global_reward_index
-        + Decimal::from_ratio(distributed_amount, state.total_bond_amount);
+    state.uncredited_rewards += distributed_amount;
+    state.reward_per_alloc_point = state.reward_per_alloc_point
+        + Decimal::from_ratio(distributed_amount, state.total_alloc_point);
+
+    Ok(())
+}
+
+// compute the pool share of distributed rewards and update pool global reward index,
+// only the amount credited to the stakers counts as distributed
+fn compute_pool_reward(state: &mut State, pool_info: &mut PoolInfo) {
+    let distributed_amount = (state.reward_per_alloc_point - pool_info.reward_per_alloc_point)
+        * Uint128::from(pool_info.alloc_point);
+    pool_info.reward_per_alloc_point = state.reward_per_alloc_point;
+    state.uncredited_rewards = state.uncredited_rewards.saturating_sub(distributed_amount);
+
+    if pool_info.total_bond_amount.is_zero() {
+        return;
+    }
+
+    let increase = Decimal::from_ratio(distributed_amount, pool_info.total_bond_amount);
+    pool_info.global_reward_index = pool_info.global_reward_index + increase;
+    state.total_distributed += pool_info.total_bond_amount * increase;
 }
 
 // withdraw reward to pending reward
-fn compute_staker_reward(state: &State, staker_info: &mut StakerInfo) -> StdResult<()> {
-    let pending_reward = (staker_info.bond_amount * state.global_reward_index)
+fn compute_staker_reward(pool_info: &PoolInfo, staker_info: &mut StakerInfo) -> StdResult<()> {
+    let pending_reward = (staker_info.bond_amount * pool_info.global_reward_index)
         .checked_sub(staker_info.bond_amount * staker_info.reward_index)?;
 
-    staker_info.reward_index = state.global_reward_index;
+    staker_info.reward_index = pool_info.global_reward_index;
     staker_info.pending_reward += pending_reward;
+
+    // a denom missing from the staker indexes was first distributed after its last sync
+    for (denom, index) in pool_info.native_reward_indexes.iter() {
+        let staker_index = staker_info
+            .native_reward_indexes
+            .iter()
+            .find(|(staker_denom, _)| staker_denom == denom)
+            .map_or_else(Decimal::zero, |(_, staker_index)| *staker_index);
+        let amount = (staker_info.bond_amount * *index)
+            .checked_sub(staker_info.bond_amount * staker_index)?;
+        if !amount.is_zero() {
+            add_coin(
+                &mut staker_info.pending_native_rewards,
+                Coin {
+                    denom: denom.to_string(),
+                    amount,
+                },
+            );
+        }
+    }
+
+    staker_info.native_reward_indexes = pool_info.native_reward_indexes.clone();
     Ok(())
 }
 
+// raise the pool reward index of the coin denom by amount / total_bond_amount
+fn increase_native_reward_index(pool_info: &mut PoolInfo, coin: &Coin) {
+    let increase = Decimal::from_ratio(coin.amount, pool_info.total_bond_amount);
+    match pool_info
+        .native_reward_indexes
+        .iter_mut()
+        .find(|(denom, _)| *denom == coin.denom)
+    {
+        Some((_, index)) => *index = *index + increase,
+        None => pool_info
+            .native_reward_indexes
+            .push((coin.denom.to_string(), increase)),
+    }
+}
+
+// add the revenue distributed while nothing was bonded to the reward indexes
+fn apply_undistributed_revenue(pool_info: &mut PoolInfo) {
+    if pool_info.total_bond_amount.is_zero() {
+        return;
+    }
+
+    if !pool_info.undistributed_revenue.is_zero() {
+        pool_info.global_reward_index = pool_info.global_reward_index
+            + Decimal::from_ratio(pool_info.undistributed_revenue, pool_info.total_bond_amount);
+        pool_info.undistributed_revenue = Uint128::zero();
+    }
+
+    for coin in std::mem::take(&mut pool_info.undistributed_native_revenue) {
+        increase_native_reward_index(pool_info, &coin);
+    }
+}
+
+// merge the coin into the list
+fn add_coin(coins: &mut Vec<Coin>, coin: Coin) {
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(c) => c.amount += coin.amount,
+        None => coins.push(coin),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::State { block_height } => to_binary(&query_state(deps, block_height)?),
+        QueryMsg::State { block_height } => to_binary(&query_state(deps, env, block_height)?),
+        QueryMsg::Pool {
+            pool_id,
+            block_height,
+        } => to_binary(&query_pool(deps, env, pool_id, block_height)?),
+        QueryMsg::Pools { start_after, limit } => {
+            to_binary(&query_pools(deps, start_after, limit)?)
+        }
         QueryMsg::StakerInfo {
+            staker,
+            pool_id,
+            block_height,
+        } => to_binary(&query_staker_info(
+            deps,
+            env,
+            staker,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            block_height,
+        )?),
+        QueryMsg::StakerInfos {
+            stakers,
+            pool_id,
+            block_height,
+        } => to_binary(&query_staker_infos(
+            deps,
+            env,
+            stakers,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            block_height,
+        )?),
+        QueryMsg::TopStakers {
+            pool_id,
+            start_after,
+            limit,
+        } => to_binary(&query_top_stakers(
+            deps,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Capacity { pool_id, staker } => to_binary(&query_capacity(
+            deps,
+            pool_id.unwrap_or(DEFAULT_POOL_ID),
+            staker,
+        )?),
+        QueryMsg::Hooks {} => to_binary(&query_hooks(deps)?),
+        QueryMsg::RewardVesting {
             staker,
             block_height,
-        } => to_binary(&query_staker_info(deps, staker, block_height)?),
+        } => to_binary(&query_reward_vesting(
+            deps,
+            staker,
+            block_height.unwrap_or(env.block.height),
+        )?),
+        QueryMsg::Allowlist { start_after, limit } => {
+            to_binary(&query_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::FrozenStakers { start_after, limit } => {
+            to_binary(&query_frozen_stakers(deps, start_after, limit)?)
+        }
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, env)?),
+        QueryMsg::PeriodFinish {} => to_binary(&query_period_finish(deps, env)?),
     }
 }
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let state = read_config(deps.storage)?;
+    let staking_token = match read_pool_info(deps.storage, DEFAULT_POOL_ID)?.staking_token {
+        AssetInfoRaw::Token { contract_addr } => {
+            deps.api.addr_humanize(&contract_addr)?.to_string()
+        }
+        AssetInfoRaw::NativeToken { denom } => denom,
+    };
+
     let resp = ConfigResponse {
         xdefi_token: deps.api.addr_humanize(&state.xdefi_token)?.to_string(),
-        staking_token: deps.api.addr_humanize(&state.staking_token)?.to_string(),
-        distribution_schedule: state.distribution_schedule,
+        staking_token,
+        distribution_schedule: read_reward_schedules(deps.storage)?,
+        reward_vesting_period: state.reward_vesting_period,
+        distributor_contract: match state.distributor_contract {
+            Some(distributor) => Some(deps.api.addr_humanize(&distributor)?.to_string()),
+            None => None,
+        },
+        trusted_forwarders: state
+            .trusted_forwarders
+            .iter()
+            .map(|forwarder| Ok(deps.api.addr_humanize(forwarder)?.to_string()))
+            .collect::<StdResult<Vec<String>>>()?,
     };
 
     Ok(resp)
 }
 
-pub fn query_state(deps: Deps, block_height: Option<u64>) -> StdResult<StateResponse> {
-    let mut state: State = read_state(deps.storage)?;
-    if let Some(block_height) = block_height {
-        let config = read_config(deps.storage)?;
-        compute_reward(&config, &mut state, block_height);
+// load the state as of the given block height: the latest checkpoint by then for past
+// heights, with the emission carried forward from its last_distributed to that height
+fn load_state_at(deps: Deps, env: &Env, block_height: u64) -> StdResult<State> {
+    let mut state: State = if block_height < env.block.height {
+        if block_height.saturating_add(CHECKPOINT_RETENTION) < env.block.height {
+            return Err(StdError::generic_err(
+                "block height is older than the checkpoint retention",
+            ));
+        }
+
+        read_state_at(deps.storage, block_height)?.ok_or_else(|| {
+            StdError::generic_err("block height is before the first stored state checkpoint")
+        })?
+    } else {
+        read_state(deps.storage)?
+    };
+
+    compute_reward(deps.storage, &mut state, block_height)?;
+    Ok(state)
+}
+
+// load the pool as of the given block height, synced with the state at that height
+fn load_pool_at(
+    deps: Deps,
+    env: &Env,
+    pool_id: u64,
+    block_height: u64,
+) -> StdResult<(State, PoolInfo)> {
+    let mut state = load_state_at(deps, env, block_height)?;
+
+    let mut pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    if block_height < env.block.height {
+        pool_info = read_pool_info_at(deps.storage, pool_id, block_height)?
+            .ok_or_else(|| StdError::generic_err("pool did not exist at the given block height"))?;
     }
 
+    compute_pool_reward(&mut state, &mut pool_info);
+    Ok((state, pool_info))
+}
+
+pub fn query_state(deps: Deps, env: Env, block_height: Option<u64>) -> StdResult<StateResponse> {
+    let (state, pool_info) = match block_height {
+        Some(block_height) => load_pool_at(deps, &env, DEFAULT_POOL_ID, block_height)?,
+        None => (
+            read_state(deps.storage)?,
+            read_pool_info(deps.storage, DEFAULT_POOL_ID)?,
+        ),
+    };
+
     Ok(StateResponse {
         last_distributed: state.last_distributed,
-        total_bond_amount: state.total_bond_amount,
-        global_reward_index: state.global_reward_index,
+        total_bond_amount: pool_info.total_bond_amount,
+        global_reward_index: pool_info.global_reward_index,
+        total_alloc_point: state.total_alloc_point,
+        reward_per_alloc_point: state.reward_per_alloc_point,
+        pool_count: state.pool_count,
         owner_address: state.owner_address,
+        total_distributed: state.total_distributed,
+        total_claimed: state.total_claimed,
+        total_vesting: state.total_vesting,
+        emission_halted: state.emission_halted,
     })
 }
 
+pub fn query_solvency(deps: Deps, env: Env) -> StdResult<SolvencyResponse> {
+    let config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    compute_reward(deps.storage, &mut state, env.block.height)?;
+
+    compute_solvency(deps, &env, &config, &state)
+}
+
+pub fn query_period_finish(deps: Deps, env: Env) -> StdResult<PeriodFinishResponse> {
+    let config: Config = read_config(deps.storage)?;
+
+    let mut reward_rate = Decimal::zero();
+    if let Some(schedule_id) = config.rolling_schedule_id {
+        if config.period_finish > env.block.height {
+            if let Some(s) = read_reward_schedules(deps.storage)?
+                .iter()
+                .find(|s| s.id == schedule_id)
+            {
+                reward_rate = Decimal::from_ratio(s.amount, s.end - s.start);
+            }
+        }
+    }
+
+    Ok(PeriodFinishResponse {
+        period_finish: config.period_finish,
+        rewards_duration: config.rewards_duration,
+        reward_rate,
+    })
+}
+
+pub fn query_pool(
+    deps: Deps,
+    env: Env,
+    pool_id: u64,
+    block_height: Option<u64>,
+) -> StdResult<PoolResponse> {
+    let (state, pool_info) = match block_height {
+        Some(block_height) => load_pool_at(deps, &env, pool_id, block_height)?,
+        None => (
+            read_state(deps.storage)?,
+            read_pool_info(deps.storage, pool_id)?,
+        ),
+    };
+
+    Ok(PoolResponse {
+        pool_id,
+        staking_token: pool_info.staking_token.to_normal(deps.api)?,
+        alloc_point: pool_info.alloc_point,
+        last_distributed: state.last_distributed,
+        total_bond_amount: pool_info.total_bond_amount,
+        global_reward_index: pool_info.global_reward_index,
+    })
+}
+
+pub fn query_pools(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let state: State = read_state(deps.storage)?;
+    let pools = read_pools(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(pool_id, pool_info)| {
+            Ok(PoolResponse {
+                pool_id,
+                staking_token: pool_info.staking_token.to_normal(deps.api)?,
+                alloc_point: pool_info.alloc_point,
+                last_distributed: state.last_distributed,
+                total_bond_amount: pool_info.total_bond_amount,
+                global_reward_index: pool_info.global_reward_index,
+            })
+        })
+        .collect::<StdResult<Vec<PoolResponse>>>()?;
+
+    Ok(PoolsResponse { pools })
+}
+
 pub fn query_staker_info(
     deps: Deps,
+    env: Env,
     staker: String,
+    pool_id: u64,
+    block_height: Option<u64>,
+) -> StdResult<StakerInfoResponse> {
+    let projection = load_staker_projection(deps, &env, pool_id, block_height)?;
+    load_staker_info_response(deps, staker, pool_id, projection.as_ref())
+}
+
+pub fn query_staker_infos(
+    deps: Deps,
+    env: Env,
+    stakers: Vec<String>,
+    pool_id: u64,
+    block_height: Option<u64>,
+) -> StdResult<StakerInfosResponse> {
+    if stakers.len() > MAX_STAKER_INFOS {
+        return Err(StdError::generic_err(format!(
+            "cannot query more than {} stakers at once",
+            MAX_STAKER_INFOS
+        )));
+    }
+
+    // the pool is projected once for the whole batch
+    let projection = load_staker_projection(deps, &env, pool_id, block_height)?;
+    let staker_infos = stakers
+        .into_iter()
+        .map(|staker| load_staker_info_response(deps, staker, pool_id, projection.as_ref()))
+        .collect::<StdResult<Vec<StakerInfoResponse>>>()?;
+
+    Ok(StakerInfosResponse { staker_infos })
+}
+
+// pool rewards projected to the queried block height, along with
+// whether the staker infos have to be read from their checkpoints
+struct StakerProjection {
+    block_height: u64,
+    historical: bool,
+    pool_info: PoolInfo,
+}
+
+fn load_staker_projection(
+    deps: Deps,
+    env: &Env,
+    pool_id: u64,
     block_height: Option<u64>,
+) -> StdResult<Option<StakerProjection>> {
+    let block_height = match block_height {
+        Some(block_height) => block_height,
+        None => return Ok(None),
+    };
+
+    let (_, pool_info) = load_pool_at(deps, env, pool_id, block_height)?;
+    Ok(Some(StakerProjection {
+        block_height,
+        historical: block_height < env.block.height,
+        pool_info,
+    }))
+}
+
+fn load_staker_info_response(
+    deps: Deps,
+    staker: String,
+    pool_id: u64,
+    projection: Option<&StakerProjection>,
 ) -> StdResult<StakerInfoResponse> {
     let staker_raw = deps.api.addr_canonicalize(&staker)?;
 
-    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &staker_raw)?;
-    if let Some(block_height) = block_height {
-        let config = read_config(deps.storage)?;
-        let mut state = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, pool_id, &staker_raw)?;
+    if let Some(projection) = projection {
+        if projection.historical {
+            staker_info =
+                read_staker_info_at(deps.storage, pool_id, &staker_raw, projection.block_height)?;
+        }
 
-        compute_reward(&config, &mut state, block_height);
-        compute_staker_reward(&state, &mut staker_info)?;
+        compute_staker_reward(&projection.pool_info, &mut staker_info)?;
     }
 
     Ok(StakerInfoResponse {
         staker,
+        pool_id,
         reward_index: staker_info.reward_index,
         bond_amount: staker_info.bond_amount,
         pending_reward: staker_info.pending_reward,
+        pending_native_rewards: staker_info.pending_native_rewards,
+        total_claimed: read_staker_claimed(deps.storage, pool_id, &staker_raw)?,
+    })
+}
+
+pub fn query_top_stakers(
+    deps: Deps,
+    pool_id: u64,
+    start_after: Option<(Uint128, String)>,
+    limit: Option<u32>,
+) -> StdResult<TopStakersResponse> {
+    // the page resumes below the bond the last staker had when it was listed
+    let start_after = match start_after {
+        Some((bond_amount, staker)) => Some((bond_amount, deps.api.addr_canonicalize(&staker)?)),
+        None => None,
+    };
+
+    let stakers = read_top_stakers(deps.storage, pool_id, start_after, limit)?
+        .into_iter()
+        .map(|(staker, bond_amount)| {
+            Ok(TopStakerResponse {
+                staker: deps.api.addr_humanize(&staker)?.to_string(),
+                bond_amount,
+            })
+        })
+        .collect::<StdResult<Vec<TopStakerResponse>>>()?;
+
+    let next_start_after = stakers
+        .last()
+        .map(|last| (last.bond_amount, last.staker.clone()));
+    Ok(TopStakersResponse {
+        stakers,
+        next_start_after,
+    })
+}
+
+pub fn query_capacity(
+    deps: Deps,
+    pool_id: u64,
+    staker: Option<String>,
+) -> StdResult<CapacityResponse> {
+    let pool_info: PoolInfo = read_pool_info(deps.storage, pool_id)?;
+    let limits = pool_info.bond_limits;
+
+    let (remaining_capacity, filled_ratio) = match limits.max_total_bond {
+        Some(max_total_bond) => (
+            Some(max_total_bond.saturating_sub(pool_info.total_bond_amount)),
+            Some(if max_total_bond.is_zero() {
+                Decimal::one()
+            } else {
+                Decimal::from_ratio(pool_info.total_bond_amount, max_total_bond)
+            }),
+        ),
+        None => (None, None),
+    };
+
+    // the staker can bond up to the tighter of both caps
+    let staker_remaining_capacity = match (staker, limits.max_bond_per_staker) {
+        (Some(staker), Some(max_bond_per_staker)) => {
+            let staker_info =
+                read_staker_info(deps.storage, pool_id, &deps.api.addr_canonicalize(&staker)?)?;
+            let remaining = max_bond_per_staker.saturating_sub(staker_info.bond_amount);
+            Some(remaining_capacity.map_or(remaining, |r| std::cmp::min(r, remaining)))
+        }
+        (Some(_), None) => remaining_capacity,
+        (None, _) => None,
+    };
+
+    Ok(CapacityResponse {
+        pool_id,
+        max_total_bond: limits.max_total_bond,
+        max_bond_per_staker: limits.max_bond_per_staker,
+        min_bond_amount: limits.min_bond_amount,
+        total_bond_amount: pool_info.total_bond_amount,
+        remaining_capacity,
+        filled_ratio,
+        staker_remaining_capacity,
+    })
+}
+
+pub fn query_reward_vesting(
+    deps: Deps,
+    staker: String,
+    block_height: u64,
+) -> StdResult<RewardVestingResponse> {
+    let staker_raw = deps.api.addr_canonicalize(&staker)?;
+    let vesting_info = read_reward_vesting(deps.storage, &staker_raw)?.unwrap_or(VestingInfo {
+        schedules: vec![],
+        last_claim_time: block_height,
+        cliff: None,
+        revocable: false,
+    });
+
+    let total: Uint128 = vesting_info.schedules.iter().map(|s| s.2).sum();
+    let released = released_amount(&vesting_info, block_height);
+
+    Ok(RewardVestingResponse {
+        staker,
+        vested: compute_vested_amount(&vesting_info, block_height),
+        unvested: total.checked_sub(released)?,
+        info: vesting_info,
+    })
+}
+
+pub fn query_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    let hooks = read_hooks(deps.storage)?
+        .iter()
+        .map(|hook| Ok(deps.api.addr_humanize(hook)?.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(HooksResponse { hooks })
+}
+
+pub fn query_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllowlistResponse> {
+    let config: Config = read_config(deps.storage)?;
+    let start_after = match start_after {
+        Some(addr) => Some(deps.api.addr_canonicalize(&addr)?),
+        None => None,
+    };
+
+    let addresses = read_allowlist(deps.storage, start_after, limit)?
+        .iter()
+        .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(AllowlistResponse {
+        enabled: config.allowlist_enabled,
+        addresses,
     })
 }
 
+pub fn query_frozen_stakers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FrozenStakersResponse> {
+    let start_after = match start_after {
+        Some(addr) => Some(deps.api.addr_canonicalize(&addr)?),
+        None => None,
+    };
+
+    let stakers = read_frozen(deps.storage, start_after, limit)?
+        .iter()
+        .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(FrozenStakersResponse { stakers })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // contracts deployed before the pools still hold the single pool layout
+    if read_pool_info(deps.storage, DEFAULT_POOL_ID).is_err() {
+        if let Some(legacy_config) = read_legacy_config(deps.storage)? {
+            migrate_single_pool(deps, env, legacy_config)?;
+        }
+    }
+
     Ok(Response::default())
 }
+
+// moves the single pool into the default pool, its cw20 staking token converted
+// to an asset and its distribution schedule to stored schedules
+fn migrate_single_pool(deps: DepsMut, env: Env, legacy_config: LegacyConfig) -> StdResult<()> {
+    let legacy_state = read_legacy_state(deps.storage)?;
+
+    for (id, (start, end, amount)) in legacy_config.distribution_schedule.iter().enumerate() {
+        store_reward_schedule(
+            deps.storage,
+            &RewardSchedule {
+                id: id as u64,
+                start: *start,
+                end: *end,
+                amount: *amount,
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+        )?;
+    }
+
+    store_config(
+        deps.storage,
+        &Config {
+            xdefi_token: legacy_config.xdefi_token.clone(),
+            next_schedule_id: legacy_config.distribution_schedule.len() as u64,
+            allowlist_enabled: false,
+            reward_vesting_period: None,
+            distributor_contract: None,
+            trusted_forwarders: vec![],
+            rewards_duration: 0,
+            period_finish: 0,
+            rolling_schedule_id: None,
+        },
+    )?;
+
+    // the single pool may stake the reward token itself
+    let total_bonded_reward_token = if legacy_config.staking_token == legacy_config.xdefi_token {
+        legacy_state.total_bond_amount
+    } else {
+        Uint128::zero()
+    };
+
+    // the single pool index carries over, the pool is synced with a fresh state
+    let pool_info = PoolInfo {
+        staking_token: AssetInfoRaw::Token {
+            contract_addr: legacy_config.staking_token,
+        },
+        alloc_point: DEFAULT_ALLOC_POINT,
+        total_bond_amount: legacy_state.total_bond_amount,
+        global_reward_index: legacy_state.global_reward_index,
+        reward_per_alloc_point: Decimal::zero(),
+        bond_limits: BondLimits::default(),
+        undistributed_revenue: Uint128::zero(),
+        native_reward_indexes: vec![],
+        undistributed_native_revenue: vec![],
+    };
+    store_pool_info(deps.storage, DEFAULT_POOL_ID, &pool_info, env.block.height)?;
+
+    // the stakers are moved in batches with `MigrateStakers`
+    store_state(
+        deps.storage,
+        &State {
+            last_distributed: legacy_state.last_distributed,
+            total_alloc_point: DEFAULT_ALLOC_POINT,
+            reward_per_alloc_point: Decimal::zero(),
+            pool_count: 1,
+            owner_address: legacy_state.owner_address,
+            total_distributed: Uint128::zero(),
+            uncredited_rewards: Uint128::zero(),
+            total_claimed: Uint128::zero(),
+            total_vesting: Uint128::zero(),
+            emission_halted: false,
+            halted_since: 0,
+            total_bonded_reward_token,
+            legacy_reward_index: Some(legacy_state.global_reward_index),
+        },
+        env.block.height,
+    )
+}
+
+/// moves the next stakers of the single pool to the default pool, synced with its index
+/// at the migration; the rewards owed to them by then count as distributed for the
+/// solvency checks, the ones bonding or withdrawing since already have a pool position
+pub fn migrate_stakers(deps: DepsMut, env: Env, limit: Option<u32>) -> StdResult<Response> {
+    let mut state: State = read_state(deps.storage)?;
+    let legacy_reward_index = match state.legacy_reward_index {
+        Some(legacy_reward_index) => legacy_reward_index,
+        None => return Err(StdError::generic_err("no stakers left to migrate")),
+    };
+
+    let limit = limit
+        .unwrap_or(DEFAULT_MIGRATE_LIMIT)
+        .min(MAX_MIGRATE_LIMIT) as usize;
+    let legacy_staker_infos = read_legacy_staker_infos(deps.storage, limit)?;
+
+    let pool_info = PoolInfo {
+        global_reward_index: legacy_reward_index,
+        native_reward_indexes: vec![],
+        ..read_pool_info(deps.storage, DEFAULT_POOL_ID)?
+    };
+    for (staker, legacy_staker_info) in legacy_staker_infos.iter() {
+        let mut staker_info = StakerInfo {
+            reward_index: legacy_staker_info.reward_index,
+            bond_amount: legacy_staker_info.bond_amount,
+            pending_reward: legacy_staker_info.pending_reward,
+            ..StakerInfo::default()
+        };
+        compute_staker_reward(&pool_info, &mut staker_info)?;
+        state.total_distributed += staker_info.pending_reward;
+
+        remove_legacy_staker_info(deps.storage, staker);
+        if !legacy_staker_info.moved_to_pool {
+            store_staker_info(
+                deps.storage,
+                DEFAULT_POOL_ID,
+                staker,
+                &staker_info,
+                env.block.height,
+            )?;
+        }
+    }
+
+    if legacy_staker_infos.len() < limit {
+        state.legacy_reward_index = None;
+    }
+    store_state(deps.storage, &state, env.block.height)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "migrate_stakers"),
+        ("migrated", &legacy_staker_infos.len().to_string()),
+        ("done", &state.legacy_reward_index.is_none().to_string()),
+    ]))
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::{Decimal, Fraction, StdError, StdResult, Uint128};
+use xdefi_token::staking::{EmissionCurve, RewardSchedule};
+
+// fixed point unit of the decay ratios, 18 decimals as Decimal
+const FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+pub fn validate_curve(curve: &EmissionCurve) -> StdResult<()> {
+    let ratio = match curve {
+        EmissionCurve::Linear => return Ok(()),
+        EmissionCurve::Step { interval, ratio } => {
+            if *interval == 0 {
+                return Err(StdError::generic_err(
+                    "Step interval has to be greater than 0",
+                ));
+            }
+            ratio
+        }
+        EmissionCurve::ExponentialDecay { ratio } => ratio,
+    };
+
+    if ratio.is_zero() || *ratio >= Decimal::one() {
+        return Err(StdError::generic_err(
+            "Decay ratio has to be between 0 and 1",
+        ));
+    }
+
+    Ok(())
+}
+
+/// rewards emitted by the schedule between the given block heights,
+/// clamped to the schedule period
+pub fn emitted_amount(schedule: &RewardSchedule, from: u64, to: u64) -> Uint128 {
+    let from = from.clamp(schedule.start, schedule.end) - schedule.start;
+    let to = to.clamp(schedule.start, schedule.end) - schedule.start;
+    if from >= to {
+        return Uint128::zero();
+    }
+
+    let total_weight = cumulative_weight(&schedule.curve, schedule.end - schedule.start);
+    schedule.amount.multiply_ratio(
+        cumulative_weight(&schedule.curve, to) - cumulative_weight(&schedule.curve, from),
+        total_weight,
+    )
+}
+
+// emission weight of the first `blocks` blocks of a schedule, only meaningful
+// relative to the weight of the whole schedule: the geometric sums of the decaying
+// curves are left multiplied by (1 - ratio), which cancels out in the ratio
+fn cumulative_weight(curve: &EmissionCurve, blocks: u64) -> Uint128 {
+    match curve {
+        EmissionCurve::Linear => Uint128::from(blocks),
+        // sum of ratio^k for k < blocks, times (1 - ratio)
+        EmissionCurve::ExponentialDecay { ratio } => Uint128::new(FRACTIONAL) - pow(*ratio, blocks),
+        // every full interval weighs interval * ratio^i, then the started one
+        EmissionCurve::Step { interval, ratio } => {
+            let steps = pow(*ratio, blocks / interval);
+            let full_intervals = (Uint128::new(FRACTIONAL) - steps) * Uint128::from(*interval);
+            let started_interval = steps.multiply_ratio(
+                Uint128::from(blocks % interval) * Uint128::new(FRACTIONAL - ratio.numerator()),
+                FRACTIONAL,
+            );
+            full_intervals + started_interval
+        }
+    }
+}
+
+// ratio^exp in FRACTIONAL units, by squaring; ratio is below 1 so nothing overflows
+fn pow(ratio: Decimal, mut exp: u64) -> Uint128 {
+    let mut base = Uint128::new(ratio.numerator());
+    let mut result = Uint128::new(FRACTIONAL);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.multiply_ratio(base, FRACTIONAL);
+        }
+        base = base.multiply_ratio(base, FRACTIONAL);
+        exp >>= 1;
+    }
+    result
+}
@@ -1,4 +1,6 @@
 pub mod contract;
+pub mod emission;
+pub mod migration;
 pub mod querier;
 pub mod state;
 
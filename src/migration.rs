@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{CanonicalAddr, Decimal, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{singleton_read, Bucket, ReadonlyBucket};
+
+static KEY_CONFIG: &[u8] = b"config";
+static KEY_STATE: &[u8] = b"state";
+
+static PREFIX_REWARD: &[u8] = b"reward";
+
+// key range of the pool staker infos under PREFIX_REWARD, up to pool id 65535
+const POOL_KEYS_START: [u8; 8] = [0, 8, 0, 0, 0, 0, 0, 0];
+const POOL_KEYS_END: [u8; 8] = [0, 8, 0, 0, 0, 0, 0, 1];
+
+/// config of the contracts deployed before the pools, staking a single cw20 token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub xdefi_token: CanonicalAddr,
+    pub staking_token: CanonicalAddr,
+    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+    pub owner_address: CanonicalAddr,
+}
+
+/// returns the config stored before the pools, the pool layout
+/// shares its key so only read it from contracts without pools
+pub fn read_legacy_config(storage: &dyn Storage) -> StdResult<Option<LegacyConfig>> {
+    singleton_read(storage, KEY_CONFIG).may_load()
+}
+
+pub fn read_legacy_state(storage: &dyn Storage) -> StdResult<LegacyState> {
+    singleton_read(storage, KEY_STATE).load()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyStakerInfo {
+    pub reward_index: Decimal,
+    pub bond_amount: Uint128,
+    pub pending_reward: Uint128,
+    /// set once the staker has a position in the default pool, only the rewards
+    /// owed by the migration are left to count then
+    #[serde(default)]
+    pub moved_to_pool: bool,
+}
+
+pub fn read_legacy_staker_info(
+    storage: &dyn Storage,
+    owner: &CanonicalAddr,
+) -> StdResult<Option<LegacyStakerInfo>> {
+    ReadonlyBucket::new(storage, PREFIX_REWARD).may_load(owner.as_slice())
+}
+
+/// returns the first staker infos stored before the pools; their keys share the prefix
+/// of the pool staker infos, which start with the length prefixed pool id and are skipped
+pub fn read_legacy_staker_infos(
+    storage: &dyn Storage,
+    limit: usize,
+) -> StdResult<Vec<(CanonicalAddr, LegacyStakerInfo)>> {
+    let bucket: ReadonlyBucket<LegacyStakerInfo> = ReadonlyBucket::new(storage, PREFIX_REWARD);
+    bucket
+        .range(None, Some(&POOL_KEYS_START), Order::Ascending)
+        .chain(bucket.range(Some(&POOL_KEYS_END), None, Order::Ascending))
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
+}
+
+/// flags the staker of the single pool as moved once the default pool stores its position
+pub fn mark_legacy_staker_moved(storage: &mut dyn Storage, owner: &CanonicalAddr) -> StdResult<()> {
+    let mut bucket: Bucket<LegacyStakerInfo> = Bucket::new(storage, PREFIX_REWARD);
+    match bucket.may_load(owner.as_slice())? {
+        Some(mut legacy) if !legacy.moved_to_pool => {
+            legacy.moved_to_pool = true;
+            bucket.save(owner.as_slice(), &legacy)
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn remove_legacy_staker_info(storage: &mut dyn Storage, owner: &CanonicalAddr) {
+    Bucket::<LegacyStakerInfo>::new(storage, PREFIX_REWARD).remove(owner.as_slice())
+}
@@ -4,9 +4,10 @@ use serde::{Deserialize, Serialize};
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, Coin, ContractResult, Empty, OwnedDeps, Querier,
-    QuerierResult, QueryRequest, SystemError, SystemResult, WasmQuery,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
-use cw20::MinterResponse;
+use cw20::{BalanceResponse, MinterResponse};
+use std::collections::HashMap;
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -26,6 +27,36 @@ pub fn mock_dependencies(
 pub struct WasmMockQuerier {
     base: MockQuerier<Empty>,
     minter_querier: MinterQuerier,
+    token_querier: TokenQuerier,
+}
+
+#[derive(Clone, Default)]
+pub struct TokenQuerier {
+    // this lets us iterate over all pairs that match the first string
+    balances: HashMap<String, HashMap<String, Uint128>>,
+}
+
+impl TokenQuerier {
+    pub fn new(balances: &[(&String, &[(&String, &Uint128)])]) -> Self {
+        TokenQuerier {
+            balances: balances_to_map(balances),
+        }
+    }
+}
+
+pub(crate) fn balances_to_map(
+    balances: &[(&String, &[(&String, &Uint128)])],
+) -> HashMap<String, HashMap<String, Uint128>> {
+    let mut balances_map: HashMap<String, HashMap<String, Uint128>> = HashMap::new();
+    for (contract_addr, balances) in balances.iter() {
+        let mut contract_balances_map: HashMap<String, Uint128> = HashMap::new();
+        for (addr, balance) in balances.iter() {
+            contract_balances_map.insert(addr.to_string(), **balance);
+        }
+
+        balances_map.insert(contract_addr.to_string(), contract_balances_map);
+    }
+    balances_map
 }
 
 #[derive(Clone, Default)]
@@ -33,6 +64,7 @@ pub struct MinterQuerier {
     minter_addr: String,
 }
 
+#[allow(dead_code)]
 impl MinterQuerier {
     pub fn new(minter: String) -> Self {
         MinterQuerier {
@@ -45,6 +77,7 @@ impl MinterQuerier {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Minter {},
+    Balance { address: String },
 }
 
 impl Querier for WasmMockQuerier {
@@ -66,16 +99,24 @@ impl Querier for WasmMockQuerier {
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match &request {
-            QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr: _,
-                msg,
-            }) => match from_binary(msg) {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
                 Ok(QueryMsg::Minter {}) => {
                     SystemResult::Ok(ContractResult::from(to_binary(&MinterResponse {
                         minter: self.minter_querier.minter_addr.clone(),
                         cap: None,
                     })))
                 }
+                Ok(QueryMsg::Balance { address }) => {
+                    // tokens without mocked balances are fully funded,
+                    // so the tests unconcerned with solvency never halt the emission
+                    let balance = match self.token_querier.balances.get(contract_addr) {
+                        Some(balances) => balances.get(&address).copied().unwrap_or_default(),
+                        None => Uint128::new(u128::MAX),
+                    };
+                    SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                        balance,
+                    })))
+                }
                 _ => panic!("query not mocked"),
             },
             _ => self.base.handle_query(request),
@@ -88,10 +129,18 @@ impl WasmMockQuerier {
         WasmMockQuerier {
             base,
             minter_querier: MinterQuerier::default(),
+            token_querier: TokenQuerier::default(),
         }
     }
 
+    #[allow(dead_code)]
     pub fn with_anc_minter(&mut self, minter: String) {
         self.minter_querier = MinterQuerier::new(minter);
     }
+
+    // configure the token owner mock querier
+    #[allow(dead_code)]
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_querier = TokenQuerier::new(balances);
+    }
 }
@@ -1,19 +1,60 @@
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage, Uint128};
+use cosmwasm_std::{CanonicalAddr, Coin, Decimal, Order, StdError, StdResult, Storage, Uint128};
 use cosmwasm_storage::{singleton, singleton_read, Bucket, ReadonlyBucket};
+use xdefi_token::asset::AssetInfoRaw;
+use xdefi_token::staking::{RewardSchedule, DEFAULT_POOL_ID};
+use xdefi_token::vesting::VestingInfo;
+
+use crate::migration::{mark_legacy_staker_moved, read_legacy_staker_info};
 
 static KEY_CONFIG: &[u8] = b"config";
 static KEY_STATE: &[u8] = b"state";
+static KEY_HOOKS: &[u8] = b"hooks";
 
+static PREFIX_POOL: &[u8] = b"pool";
 static PREFIX_REWARD: &[u8] = b"reward";
+static PREFIX_STATE_CHECKPOINT: &[u8] = b"state_checkpoint";
+static PREFIX_POOL_CHECKPOINT: &[u8] = b"pool_checkpoint";
+static PREFIX_STAKER_CHECKPOINT: &[u8] = b"staker_checkpoint";
+static PREFIX_ALLOWLIST: &[u8] = b"allowlist";
+static PREFIX_FROZEN: &[u8] = b"frozen";
+static PREFIX_REWARD_VESTING: &[u8] = b"reward_vesting";
+static PREFIX_BOND_INDEX: &[u8] = b"bond_index";
+static PREFIX_STAKER_CLAIMED: &[u8] = b"staker_claimed";
+static PREFIX_REWARD_SCHEDULE: &[u8] = b"reward_schedule";
+static PREFIX_ARCHIVED_SCHEDULE: &[u8] = b"archived_schedule";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// number of blocks the state, pool and staker checkpoints are kept for,
+/// older ones are pruned as new checkpoints are written
+pub const CHECKPOINT_RETENTION: u64 = 100_000;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub xdefi_token: CanonicalAddr,
-    pub staking_token: CanonicalAddr,
-    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+    /// id of the next schedule added, the schedules are stored on their own
+    pub next_schedule_id: u64,
+    /// only allowlisted addresses can bond when enabled
+    pub allowlist_enabled: bool,
+    /// withdrawn rewards vest linearly over this number of blocks when set
+    pub reward_vesting_period: Option<u64>,
+    /// rewards are paid out by the distributor contract when set
+    pub distributor_contract: Option<CanonicalAddr>,
+    /// contracts trusted to bond on behalf of other stakers
+    #[serde(default)]
+    pub trusted_forwarders: Vec<CanonicalAddr>,
+    /// length of the rolling reward periods started by `NotifyRewardAmount`, zero until set
+    pub rewards_duration: u64,
+    /// end of the current rolling reward period
+    pub period_finish: u64,
+    /// schedule emitting the current rolling reward period
+    pub rolling_schedule_id: Option<u64>,
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -27,12 +68,40 @@ pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub last_distributed: u64,
-    pub total_bond_amount: Uint128,
-    pub global_reward_index: Decimal,
+    pub total_alloc_point: u64,
+    pub reward_per_alloc_point: Decimal,
+    pub pool_count: u64,
     pub owner_address: CanonicalAddr,
+    /// rewards credited to the pool stakers
+    #[serde(default)]
+    pub total_distributed: Uint128,
+    /// rewards emitted to the pools which were not synced since
+    #[serde(default)]
+    pub uncredited_rewards: Uint128,
+    #[serde(default)]
+    pub total_claimed: Uint128,
+    #[serde(default)]
+    pub total_vesting: Uint128,
+    #[serde(default)]
+    pub emission_halted: bool,
+    /// block height the emission was halted at, the rewards scheduled
+    /// since then are emitted once it is resumed
+    #[serde(default)]
+    pub halted_since: u64,
+    /// reward tokens bonded in the pools staking them
+    #[serde(default)]
+    pub total_bonded_reward_token: Uint128,
+    /// index of the single pool when it was migrated to the default pool,
+    /// set until its stakers are migrated with `MigrateStakers`
+    #[serde(default)]
+    pub legacy_reward_index: Option<Decimal>,
 }
 
-pub fn store_state(storage: &mut dyn Storage, state: &State) -> StdResult<()> {
+/// stores the state and checkpoints it at the given block height;
+/// the schedules ended by its last_distributed height are fully distributed and get archived
+pub fn store_state(storage: &mut dyn Storage, state: &State, block_height: u64) -> StdResult<()> {
+    archive_reward_schedules(storage, state.last_distributed)?;
+    save_checkpoint(storage, &[PREFIX_STATE_CHECKPOINT], block_height, state)?;
     singleton(storage, KEY_STATE).save(state)
 }
 
@@ -40,36 +109,503 @@ pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
     singleton_read(storage, KEY_STATE).load()
 }
 
+/// returns the latest state checkpoint at or before the given block height
+pub fn read_state_at(storage: &dyn Storage, block_height: u64) -> StdResult<Option<State>> {
+    let end = (block_height + 1).to_be_bytes();
+    ReadonlyBucket::new(storage, PREFIX_STATE_CHECKPOINT)
+        .range(None, Some(&end), Order::Descending)
+        .next()
+        .map(|item| item.map(|(_, state)| state))
+        .transpose()
+}
+
+// saves the checkpoint and prunes the ones older than the retention window,
+// except the latest of them which still holds the values at the window start
+fn save_checkpoint<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    namespaces: &[&[u8]],
+    block_height: u64,
+    value: &T,
+) -> StdResult<()> {
+    let mut bucket = Bucket::multilevel(storage, namespaces);
+    bucket.save(&block_height.to_be_bytes(), value)?;
+
+    let end = (block_height.saturating_sub(CHECKPOINT_RETENTION) + 1).to_be_bytes();
+    let pruned = bucket
+        .range(None, Some(&end), Order::Descending)
+        .skip(1)
+        .map(|item| item.map(|(k, _)| k))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    for key in pruned {
+        bucket.remove(&key);
+    }
+
+    Ok(())
+}
+
+// schedules are sorted by start block, archived ones by end block
+fn reward_schedule_key(block_height: u64, id: u64) -> Vec<u8> {
+    [block_height.to_be_bytes(), id.to_be_bytes()].concat()
+}
+
+/// stores a schedule not fully distributed yet
+pub fn store_reward_schedule(
+    storage: &mut dyn Storage,
+    schedule: &RewardSchedule,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_REWARD_SCHEDULE)
+        .save(&reward_schedule_key(schedule.start, schedule.id), schedule)
+}
+
+pub fn remove_reward_schedule(storage: &mut dyn Storage, schedule: &RewardSchedule) {
+    Bucket::<RewardSchedule>::new(storage, PREFIX_REWARD_SCHEDULE)
+        .remove(&reward_schedule_key(schedule.start, schedule.id))
+}
+
+/// returns the schedules not archived yet, sorted by start block
+pub fn read_reward_schedules(storage: &dyn Storage) -> StdResult<Vec<RewardSchedule>> {
+    ReadonlyBucket::new(storage, PREFIX_REWARD_SCHEDULE)
+        .range(None, None, Order::Ascending)
+        .map(|item| item.map(|(_, schedule)| schedule))
+        .collect()
+}
+
+/// returns the archived schedules, sorted by end block
+pub fn read_archived_reward_schedules(storage: &dyn Storage) -> StdResult<Vec<RewardSchedule>> {
+    ReadonlyBucket::new(storage, PREFIX_ARCHIVED_SCHEDULE)
+        .range(None, None, Order::Ascending)
+        .map(|item| item.map(|(_, schedule)| schedule))
+        .collect()
+}
+
+/// returns the schedules which may overlap the given block range:
+/// the archived ones ended at or after its start and the others started by its end
+pub fn read_reward_schedules_between(
+    storage: &dyn Storage,
+    start: u64,
+    end: u64,
+) -> StdResult<Vec<RewardSchedule>> {
+    let archived = ReadonlyBucket::new(storage, PREFIX_ARCHIVED_SCHEDULE);
+    let active = ReadonlyBucket::new(storage, PREFIX_REWARD_SCHEDULE);
+
+    let active_end = end.checked_add(1).map(|end| end.to_be_bytes());
+    archived
+        .range(Some(&start.to_be_bytes()), None, Order::Ascending)
+        .chain(active.range(
+            None,
+            active_end.as_ref().map(|end| &end[..]),
+            Order::Ascending,
+        ))
+        .map(|item| item.map(|(_, schedule)| schedule))
+        .collect()
+}
+
+// move the schedules ended by the given block height to the archive
+fn archive_reward_schedules(storage: &mut dyn Storage, block_height: u64) -> StdResult<()> {
+    // only the schedules started by then may have ended
+    let ended: Vec<RewardSchedule> =
+        ReadonlyBucket::<RewardSchedule>::new(storage, PREFIX_REWARD_SCHEDULE)
+            .range(
+                None,
+                Some(&(block_height + 1).to_be_bytes()),
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(_, schedule)| schedule))
+            .filter(|schedule| schedule.as_ref().map_or(true, |s| s.end <= block_height))
+            .collect::<StdResult<_>>()?;
+
+    for schedule in ended {
+        remove_reward_schedule(storage, &schedule);
+        Bucket::new(storage, PREFIX_ARCHIVED_SCHEDULE)
+            .save(&reward_schedule_key(schedule.end, schedule.id), &schedule)?;
+    }
+
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub staking_token: AssetInfoRaw,
+    pub alloc_point: u64,
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+    /// state reward_per_alloc_point the pool was last synced with
+    pub reward_per_alloc_point: Decimal,
+    pub bond_limits: BondLimits,
+    /// xdefi distributed at once while nothing was bonded, added to the index on the next bond
+    #[serde(default)]
+    pub undistributed_revenue: Uint128,
+    /// reward index of every native denom distributed at once
+    #[serde(default)]
+    pub native_reward_indexes: Vec<(String, Decimal)>,
+    /// native coins distributed at once while nothing was bonded
+    #[serde(default)]
+    pub undistributed_native_revenue: Vec<Coin>,
+}
+
+/// owner configured bond caps, none of them is enforced when unset
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct BondLimits {
+    pub max_total_bond: Option<Uint128>,
+    pub max_bond_per_staker: Option<Uint128>,
+    pub min_bond_amount: Option<Uint128>,
+}
+
+/// stores pool_info of the given pool and checkpoints it at the given block height
+pub fn store_pool_info(
+    storage: &mut dyn Storage,
+    pool_id: u64,
+    pool_info: &PoolInfo,
+    block_height: u64,
+) -> StdResult<()> {
+    save_checkpoint(
+        storage,
+        &[PREFIX_POOL_CHECKPOINT, &pool_id.to_be_bytes()],
+        block_height,
+        pool_info,
+    )?;
+    Bucket::new(storage, PREFIX_POOL).save(&pool_id.to_be_bytes(), pool_info)
+}
+
+pub fn read_pool_info(storage: &dyn Storage, pool_id: u64) -> StdResult<PoolInfo> {
+    match ReadonlyBucket::new(storage, PREFIX_POOL).may_load(&pool_id.to_be_bytes())? {
+        Some(pool_info) => Ok(pool_info),
+        None => Err(StdError::generic_err("pool does not exist")),
+    }
+}
+
+/// returns the latest pool_info checkpoint at or before the given block height
+pub fn read_pool_info_at(
+    storage: &dyn Storage,
+    pool_id: u64,
+    block_height: u64,
+) -> StdResult<Option<PoolInfo>> {
+    let end = (block_height + 1).to_be_bytes();
+    ReadonlyBucket::multilevel(storage, &[PREFIX_POOL_CHECKPOINT, &pool_id.to_be_bytes()])
+        .range(None, Some(&end), Order::Descending)
+        .next()
+        .map(|item| item.map(|(_, pool_info)| pool_info))
+        .transpose()
+}
+
+pub fn read_pools(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, PoolInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| (id + 1).to_be_bytes());
+
+    ReadonlyBucket::new(storage, PREFIX_POOL)
+        .range(start.as_ref().map(|s| &s[..]), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, pool_info) = item?;
+            let mut pool_id = [0u8; 8];
+            pool_id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(pool_id), pool_info))
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
 pub struct StakerInfo {
     pub reward_index: Decimal,
     pub bond_amount: Uint128,
     pub pending_reward: Uint128,
+    /// pool native_reward_indexes the staker was last synced with
+    #[serde(default)]
+    pub native_reward_indexes: Vec<(String, Decimal)>,
+    #[serde(default)]
+    pub pending_native_rewards: Vec<Coin>,
 }
 
-/// returns return staker_info of the given owner
+/// stores staker_info of the given owner and checkpoints it at the given block height
 pub fn store_staker_info(
     storage: &mut dyn Storage,
+    pool_id: u64,
     owner: &CanonicalAddr,
     staker_info: &StakerInfo,
+    block_height: u64,
 ) -> StdResult<()> {
-    Bucket::new(storage, PREFIX_REWARD).save(owner.as_slice(), staker_info)
+    save_checkpoint(
+        storage,
+        &[
+            PREFIX_STAKER_CHECKPOINT,
+            &pool_id.to_be_bytes(),
+            owner.as_slice(),
+        ],
+        block_height,
+        staker_info,
+    )?;
+
+    let old_bond = read_staker_info(storage, pool_id, owner)?.bond_amount;
+    update_bond_index(storage, pool_id, owner, old_bond, staker_info.bond_amount)?;
+    if pool_id == DEFAULT_POOL_ID {
+        mark_legacy_staker_moved(storage, owner)?;
+    }
+
+    Bucket::multilevel(storage, &[PREFIX_REWARD, &pool_id.to_be_bytes()])
+        .save(owner.as_slice(), staker_info)
 }
 
-/// remove staker_info of the given owner
-pub fn remove_staker_info(storage: &mut dyn Storage, owner: &CanonicalAddr) {
-    Bucket::<StakerInfo>::new(storage, PREFIX_REWARD).remove(owner.as_slice())
+/// remove staker_info of the given owner, checkpointing an empty position
+pub fn remove_staker_info(
+    storage: &mut dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+    block_height: u64,
+) -> StdResult<()> {
+    save_checkpoint(
+        storage,
+        &[
+            PREFIX_STAKER_CHECKPOINT,
+            &pool_id.to_be_bytes(),
+            owner.as_slice(),
+        ],
+        block_height,
+        &StakerInfo::default(),
+    )?;
+
+    let old_bond = read_staker_info(storage, pool_id, owner)?.bond_amount;
+    update_bond_index(storage, pool_id, owner, old_bond, Uint128::zero())?;
+    if pool_id == DEFAULT_POOL_ID {
+        mark_legacy_staker_moved(storage, owner)?;
+    }
+
+    Bucket::<StakerInfo>::multilevel(storage, &[PREFIX_REWARD, &pool_id.to_be_bytes()])
+        .remove(owner.as_slice());
+    Ok(())
+}
+
+// bond index keys sort by bond amount first, then by address
+fn bond_index_key(bond_amount: Uint128, owner: &CanonicalAddr) -> Vec<u8> {
+    [&bond_amount.u128().to_be_bytes()[..], owner.as_slice()].concat()
+}
+
+// move the staker to its new bond in the bond index, stakers without bond are not indexed
+fn update_bond_index(
+    storage: &mut dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+    old_bond: Uint128,
+    new_bond: Uint128,
+) -> StdResult<()> {
+    if old_bond == new_bond {
+        return Ok(());
+    }
+
+    let mut bucket =
+        Bucket::<bool>::multilevel(storage, &[PREFIX_BOND_INDEX, &pool_id.to_be_bytes()]);
+    if !old_bond.is_zero() {
+        bucket.remove(&bond_index_key(old_bond, owner));
+    }
+
+    if !new_bond.is_zero() {
+        bucket.save(&bond_index_key(new_bond, owner), &true)?;
+    }
+
+    Ok(())
+}
+
+/// returns the pool stakers with their bond amount, largest bond first;
+/// start_after is the bond amount and address of the last staker of the previous page
+pub fn read_top_stakers(
+    storage: &dyn Storage,
+    pool_id: u64,
+    start_after: Option<(Uint128, CanonicalAddr)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(CanonicalAddr, Uint128)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(|(bond_amount, owner)| bond_index_key(bond_amount, &owner));
+
+    ReadonlyBucket::<bool>::multilevel(storage, &[PREFIX_BOND_INDEX, &pool_id.to_be_bytes()])
+        .range(None, end.as_deref(), Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            let mut bond_amount = [0u8; 16];
+            bond_amount.copy_from_slice(&k[..16]);
+            Ok((
+                CanonicalAddr::from(k[16..].to_vec()),
+                Uint128::from(u128::from_be_bytes(bond_amount)),
+            ))
+        })
+        .collect()
 }
 
 /// returns rewards owned by this owner
 /// (read-only version for queries)
-pub fn read_staker_info(storage: &dyn Storage, owner: &CanonicalAddr) -> StdResult<StakerInfo> {
-    match ReadonlyBucket::new(storage, PREFIX_REWARD).may_load(owner.as_slice())? {
+pub fn read_staker_info(
+    storage: &dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+) -> StdResult<StakerInfo> {
+    match ReadonlyBucket::multilevel(storage, &[PREFIX_REWARD, &pool_id.to_be_bytes()])
+        .may_load(owner.as_slice())?
+    {
         Some(staker_info) => Ok(staker_info),
-        None => Ok(StakerInfo {
-            reward_index: Decimal::zero(),
-            bond_amount: Uint128::zero(),
-            pending_reward: Uint128::zero(),
-        }),
+        // the stakers of the single pool not migrated yet are read from its layout
+        None if pool_id == DEFAULT_POOL_ID => match read_legacy_staker_info(storage, owner)? {
+            Some(legacy) if !legacy.moved_to_pool => Ok(StakerInfo {
+                reward_index: legacy.reward_index,
+                bond_amount: legacy.bond_amount,
+                pending_reward: legacy.pending_reward,
+                ..StakerInfo::default()
+            }),
+            _ => Ok(StakerInfo::default()),
+        },
+        None => Ok(StakerInfo::default()),
     }
 }
+
+/// returns the latest staker_info checkpoint of the owner at or before the given block height
+pub fn read_staker_info_at(
+    storage: &dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+    block_height: u64,
+) -> StdResult<StakerInfo> {
+    let end = (block_height + 1).to_be_bytes();
+    match ReadonlyBucket::multilevel(
+        storage,
+        &[
+            PREFIX_STAKER_CHECKPOINT,
+            &pool_id.to_be_bytes(),
+            owner.as_slice(),
+        ],
+    )
+    .range(None, Some(&end), Order::Descending)
+    .next()
+    {
+        Some(item) => Ok(item?.1),
+        None => Ok(StakerInfo::default()),
+    }
+}
+
+/// adds to the rewards claimed by the staker from the pool,
+/// kept apart from staker_info so it outlives the position
+pub fn increase_staker_claimed(
+    storage: &mut dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let claimed = read_staker_claimed(storage, pool_id, owner)?;
+    Bucket::multilevel(storage, &[PREFIX_STAKER_CLAIMED, &pool_id.to_be_bytes()])
+        .save(owner.as_slice(), &claimed.checked_add(amount)?)
+}
+
+pub fn read_staker_claimed(
+    storage: &dyn Storage,
+    pool_id: u64,
+    owner: &CanonicalAddr,
+) -> StdResult<Uint128> {
+    Ok(
+        ReadonlyBucket::multilevel(storage, &[PREFIX_STAKER_CLAIMED, &pool_id.to_be_bytes()])
+            .may_load(owner.as_slice())?
+            .unwrap_or_default(),
+    )
+}
+
+/// stores the reward vesting ledger of the staker, in block heights
+pub fn store_reward_vesting(
+    storage: &mut dyn Storage,
+    staker: &CanonicalAddr,
+    vesting_info: &VestingInfo,
+) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_REWARD_VESTING).save(staker.as_slice(), vesting_info)
+}
+
+pub fn remove_reward_vesting(storage: &mut dyn Storage, staker: &CanonicalAddr) {
+    Bucket::<VestingInfo>::new(storage, PREFIX_REWARD_VESTING).remove(staker.as_slice())
+}
+
+pub fn read_reward_vesting(
+    storage: &dyn Storage,
+    staker: &CanonicalAddr,
+) -> StdResult<Option<VestingInfo>> {
+    ReadonlyBucket::new(storage, PREFIX_REWARD_VESTING).may_load(staker.as_slice())
+}
+
+pub fn store_hooks(storage: &mut dyn Storage, hooks: &[CanonicalAddr]) -> StdResult<()> {
+    singleton(storage, KEY_HOOKS).save(&hooks.to_vec())
+}
+
+pub fn read_hooks(storage: &dyn Storage) -> StdResult<Vec<CanonicalAddr>> {
+    Ok(singleton_read(storage, KEY_HOOKS)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_allowlisted(storage: &mut dyn Storage, addr: &CanonicalAddr) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_ALLOWLIST).save(addr.as_slice(), &true)
+}
+
+pub fn remove_allowlisted(storage: &mut dyn Storage, addr: &CanonicalAddr) {
+    Bucket::<bool>::new(storage, PREFIX_ALLOWLIST).remove(addr.as_slice())
+}
+
+pub fn is_allowlisted(storage: &dyn Storage, addr: &CanonicalAddr) -> StdResult<bool> {
+    Ok(ReadonlyBucket::<bool>::new(storage, PREFIX_ALLOWLIST)
+        .may_load(addr.as_slice())?
+        .is_some())
+}
+
+pub fn read_allowlist(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CanonicalAddr>> {
+    read_addresses(storage, PREFIX_ALLOWLIST, start_after, limit)
+}
+
+pub fn store_frozen(storage: &mut dyn Storage, addr: &CanonicalAddr) -> StdResult<()> {
+    Bucket::new(storage, PREFIX_FROZEN).save(addr.as_slice(), &true)
+}
+
+pub fn remove_frozen(storage: &mut dyn Storage, addr: &CanonicalAddr) {
+    Bucket::<bool>::new(storage, PREFIX_FROZEN).remove(addr.as_slice())
+}
+
+pub fn is_frozen(storage: &dyn Storage, addr: &CanonicalAddr) -> StdResult<bool> {
+    Ok(ReadonlyBucket::<bool>::new(storage, PREFIX_FROZEN)
+        .may_load(addr.as_slice())?
+        .is_some())
+}
+
+pub fn read_frozen(
+    storage: &dyn Storage,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CanonicalAddr>> {
+    read_addresses(storage, PREFIX_FROZEN, start_after, limit)
+}
+
+fn read_addresses(
+    storage: &dyn Storage,
+    prefix: &[u8],
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CanonicalAddr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    ReadonlyBucket::<bool>::new(storage, prefix)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            Ok(CanonicalAddr::from(k))
+        })
+        .collect()
+}
+
+// this will set the first key after the provided key, by appending a 1 byte
+fn calc_range_start(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(1);
+        v
+    })
+}
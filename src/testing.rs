@@ -1,14 +1,30 @@
-use crate::contract::{execute, instantiate, query};
-use crate::mock_querier::mock_dependencies;
-use cosmwasm_std::testing::{mock_env, mock_info};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::migration::{LegacyConfig, LegacyStakerInfo, LegacyState};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+use crate::state::CHECKPOINT_RETENTION;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Api, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, Decimal, Deps, Env, Order,
+    OwnedDeps, Pair, StdError, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cosmwasm_storage::{singleton, to_length_prefixed, Bucket};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use std::cell::Cell;
+use xdefi_token::asset::AssetInfo;
+use xdefi_token::distributor::ExecuteMsg as DistributorExecuteMsg;
+use xdefi_token::gov::Cw20HookMsg as GovCw20HookMsg;
+use xdefi_token::staking::events::{
+    parse_events, BondEvent, ChangeOwnerEvent, DistributeNowEvent, InsolvencyAlertEvent,
+    StakingEvent, UnbondEvent, VestRewardsEvent, WithdrawEvent,
+};
 use xdefi_token::staking::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
+    AddRewardResponse, AllowlistResponse, CapacityResponse, ConfigResponse, Cw20HookMsg,
+    EmissionCurve, ExecuteMsg, FrozenStakersResponse, HooksResponse, InstantiateMsg, MigrateMsg,
+    PeriodFinishResponse, PoolResponse, PoolsResponse, QueryMsg, RewardSchedule,
+    RewardVestingResponse, SolvencyResponse, StakeChangedHookMsg, StakerInfoResponse,
+    StakerInfosResponse, StakingTokenSolvency, StateResponse, TopStakersResponse, MAX_STAKER_INFOS,
 };
+use xdefi_token::vesting::VestingInfo;
 
 #[test]
 fn proper_initialization() {
@@ -16,7 +32,9 @@ fn proper_initialization() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![(100, 200, Uint128::from(1000000u128))],
     };
 
@@ -33,7 +51,11 @@ fn proper_initialization() {
         ConfigResponse {
             xdefi_token: "reward0000".to_string(),
             staking_token: "staking0000".to_string(),
-            distribution_schedule: vec![(100, 200, Uint128::from(1000000u128))],
+            // the schedule ended before the instantiation, it is archived right away
+            distribution_schedule: vec![],
+            reward_vesting_period: None,
+            distributor_contract: None,
+            trusted_forwarders: vec![],
         }
     );
 
@@ -50,7 +72,39 @@ fn proper_initialization() {
             last_distributed: 12345,
             total_bond_amount: Uint128::zero(),
             global_reward_index: Decimal::zero(),
-            owner_address: state.owner_address.clone()
+            total_alloc_point: 1,
+            reward_per_alloc_point: Decimal::zero(),
+            pool_count: 1,
+            owner_address: state.owner_address.clone(),
+            total_distributed: Uint128::zero(),
+            total_claimed: Uint128::zero(),
+            total_vesting: Uint128::zero(),
+            emission_halted: false,
+        }
+    );
+
+    // the default pool is created with the staking token
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Pool {
+            pool_id: 0,
+            block_height: None,
+        },
+    )
+    .unwrap();
+    let pool: PoolResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pool,
+        PoolResponse {
+            pool_id: 0,
+            staking_token: AssetInfo::Token {
+                contract_addr: "staking0000".to_string(),
+            },
+            alloc_point: 1,
+            last_distributed: 12345,
+            total_bond_amount: Uint128::zero(),
+            global_reward_index: Decimal::zero(),
         }
     );
 }
@@ -61,7 +115,9 @@ fn test_bond_tokens() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -74,7 +130,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info("staking0000", &[]);
@@ -88,6 +148,7 @@ fn test_bond_tokens() {
                 mock_env(),
                 QueryMsg::StakerInfo {
                     staker: "addr0000".to_string(),
+                    pool_id: None,
                     block_height: None,
                 },
             )
@@ -96,27 +157,37 @@ fn test_bond_tokens() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
+            pool_id: 0,
             reward_index: Decimal::zero(),
             pending_reward: Uint128::zero(),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
             bond_amount: Uint128::from(100u128),
         }
     );
-    let state = from_binary::<StateResponse>(
+    let pool = from_binary::<PoolResponse>(
         &query(
             deps.as_ref(),
             mock_env(),
-            QueryMsg::State { block_height: None },
+            QueryMsg::Pool {
+                pool_id: 0,
+                block_height: None,
+            },
         )
         .unwrap(),
     )
     .unwrap();
     assert_eq!(
-        state,
-        StateResponse {
+        pool,
+        PoolResponse {
+            pool_id: 0,
+            staking_token: AssetInfo::Token {
+                contract_addr: "staking0000".to_string(),
+            },
+            alloc_point: 1,
             total_bond_amount: Uint128::from(100u128),
             global_reward_index: Decimal::zero(),
             last_distributed: 12345,
-            owner_address: state.owner_address.clone()
         }
     );
 
@@ -124,7 +195,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     env.block.height += 10;
 
@@ -137,6 +212,7 @@ fn test_bond_tokens() {
                 mock_env(),
                 QueryMsg::StakerInfo {
                     staker: "addr0000".to_string(),
+                    pool_id: None,
                     block_height: None,
                 },
             )
@@ -145,27 +221,37 @@ fn test_bond_tokens() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
+            pool_id: 0,
             reward_index: Decimal::from_ratio(1000u128, 1u128),
             pending_reward: Uint128::from(100000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
             bond_amount: Uint128::from(200u128),
         }
     );
-    let state = from_binary::<StateResponse>(
+    let pool = from_binary::<PoolResponse>(
         &query(
             deps.as_ref(),
             mock_env(),
-            QueryMsg::State { block_height: None },
+            QueryMsg::Pool {
+                pool_id: 0,
+                block_height: None,
+            },
         )
         .unwrap(),
     )
     .unwrap();
     assert_eq!(
-        state,
-        StateResponse {
+        pool,
+        PoolResponse {
+            pool_id: 0,
+            staking_token: AssetInfo::Token {
+                contract_addr: "staking0000".to_string(),
+            },
+            alloc_point: 1,
             total_bond_amount: Uint128::from(200u128),
             global_reward_index: Decimal::from_ratio(1000u128, 1u128),
             last_distributed: 12345 + 10,
-            owner_address: state.owner_address.clone()
         }
     );
 
@@ -173,7 +259,11 @@ fn test_bond_tokens() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
 
     let info = mock_info("staking0001", &[]);
@@ -184,13 +274,123 @@ fn test_bond_tokens() {
     }
 }
 
+#[test]
+fn test_bond_on_behalf() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the airdrop bonds the tokens it sends for another staker
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "airdrop0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: Some("addr0001".to_string()),
+        })
+        .unwrap(),
+    });
+
+    // untrusted senders can't bond on behalf of other stakers
+    let info = mock_info("staking0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let trust_airdrop = ExecuteMsg::UpdateTrustedForwarders {
+        add: Some(vec!["airdrop0000".to_string()]),
+        remove: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        trust_airdrop.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        trust_airdrop,
+    )
+    .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "bond"),
+            attr("pool_id", "0"),
+            attr("owner", "addr0001"),
+            attr("amount", "100"),
+        ]
+    );
+
+    let query_bond = |staker: &str| -> Uint128 {
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: staker.to_string(),
+                    pool_id: None,
+                    block_height: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .bond_amount
+    };
+    assert_eq!(query_bond("addr0001"), Uint128::from(100u128));
+    assert_eq!(query_bond("airdrop0000"), Uint128::zero());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.trusted_forwarders, vec!["airdrop0000".to_string()]);
+
+    // removed forwarders are no longer honoured
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateTrustedForwarders {
+            add: None,
+            remove: Some(vec!["airdrop0000".to_string()]),
+        },
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+}
+
 #[test]
 fn test_unbond() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -204,14 +404,20 @@ fn test_unbond() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     // unbond 150 tokens; failed
     let msg = ExecuteMsg::Unbond {
+        pool_id: None,
         amount: Uint128::from(150u128),
+        claim_rewards: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -225,7 +431,9 @@ fn test_unbond() {
 
     // normal unbond
     let msg = ExecuteMsg::Unbond {
+        pool_id: None,
         amount: Uint128::from(100u128),
+        claim_rewards: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -250,7 +458,9 @@ fn test_compute_reward() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -264,7 +474,11 @@ fn test_compute_reward() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -278,7 +492,11 @@ fn test_compute_reward() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
@@ -289,6 +507,7 @@ fn test_compute_reward() {
                 mock_env(),
                 QueryMsg::StakerInfo {
                     staker: "addr0000".to_string(),
+                    pool_id: None,
                     block_height: None,
                 },
             )
@@ -297,8 +516,11 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
+            pool_id: 0,
             reward_index: Decimal::from_ratio(10000u128, 1u128),
             pending_reward: Uint128::from(1000000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
             bond_amount: Uint128::from(200u128),
         }
     );
@@ -310,7 +532,9 @@ fn test_compute_reward() {
 
     // unbond
     let msg = ExecuteMsg::Unbond {
+        pool_id: None,
         amount: Uint128::from(100u128),
+        claim_rewards: None,
     };
     let _res = execute(deps.as_mut(), env, info, msg).unwrap();
     assert_eq!(
@@ -320,6 +544,7 @@ fn test_compute_reward() {
                 mock_env(),
                 QueryMsg::StakerInfo {
                     staker: "addr0000".to_string(),
+                    pool_id: None,
                     block_height: None,
                 },
             )
@@ -328,8 +553,11 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
+            pool_id: 0,
             reward_index: Decimal::from_ratio(15000u64, 1u64),
             pending_reward: Uint128::from(2000000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
             bond_amount: Uint128::from(100u128),
         }
     );
@@ -342,6 +570,7 @@ fn test_compute_reward() {
                 mock_env(),
                 QueryMsg::StakerInfo {
                     staker: "addr0000".to_string(),
+                    pool_id: None,
                     block_height: Some(12345 + 120),
                 },
             )
@@ -350,8 +579,11 @@ fn test_compute_reward() {
         .unwrap(),
         StakerInfoResponse {
             staker: "addr0000".to_string(),
+            pool_id: 0,
             reward_index: Decimal::from_ratio(25000u64, 1u64),
             pending_reward: Uint128::from(3000000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
             bond_amount: Uint128::from(100u128),
         }
     );
@@ -363,7 +595,9 @@ fn test_withdraw() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -377,7 +611,11 @@ fn test_withdraw() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -388,7 +626,7 @@ fn test_withdraw() {
     env.block.height += 100;
     let info = mock_info("addr0000", &[]);
 
-    let msg = ExecuteMsg::Withdraw {};
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
 
     assert_eq!(
@@ -411,7 +649,9 @@ fn test_migrate_staking() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -425,7 +665,11 @@ fn test_migrate_staking() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(100u128),
-        msg: to_binary(&Cw20HookMsg::Bond {}).unwrap(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
     });
     let info = mock_info("staking0000", &[]);
     let mut env = mock_env();
@@ -436,7 +680,7 @@ fn test_migrate_staking() {
     env.block.height += 100;
     let info = mock_info("addr0000", &[]);
 
-    let msg = ExecuteMsg::Withdraw {};
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
     let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
     assert_eq!(
@@ -501,10 +745,12 @@ fn test_migrate_staking() {
         ConfigResponse {
             xdefi_token: "reward0000".to_string(),
             staking_token: "staking0000".to_string(),
-            distribution_schedule: vec![
-                (12345, 12345 + 100, Uint128::from(1000000u128)),
-                (12345 + 100, 12345 + 150, Uint128::from(5000000u128)), // slot was modified
-            ]
+            // the second slot was cut at the migration height,
+            // both are fully distributed and archived
+            distribution_schedule: vec![],
+            reward_vesting_period: None,
+            distributor_contract: None,
+            trusted_forwarders: vec![],
         }
     );
 }
@@ -515,7 +761,9 @@ fn test_change_owner() {
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -555,6 +803,19 @@ fn test_change_owner() {
     //has to be successful attempt
     let info = mock_info("addr0000", &[]);
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "change_owner"),
+            attr("new_owner", "newaddr0000"),
+        ]
+    );
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![StakingEvent::ChangeOwner(ChangeOwnerEvent {
+            new_owner: "newaddr0000".to_string(),
+        })]
+    );
 
     let state = from_binary::<StateResponse>(
         &query(
@@ -572,17 +833,15 @@ fn test_change_owner() {
     );
 }
 
-<<<<<<< HEAD
-// add check begin block has to be 24 hours minimum after current block height
 #[test]
-=======
->>>>>>> 073d9944f18de4cf7de2ec30704c0ec444ea6c26
 fn test_add_reward_schedule() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
         distribution_schedule: vec![
             (12345, 12345 + 100, Uint128::from(1000000u128)),
             (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
@@ -607,6 +866,8 @@ fn test_add_reward_schedule() {
 
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (2000, 2500, Uint128::from(1000000u128)),
+        label: None,
+        curve: None,
     };
 
     let mut env = mock_env();
@@ -633,6 +894,8 @@ fn test_add_reward_schedule() {
     //we try to put a reward with 0 emission
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (123450 + 300, 123450 + 700, Uint128::from(0u128)),
+        label: None,
+        curve: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -648,6 +911,8 @@ fn test_add_reward_schedule() {
     //we try to put a reward with beginning block > end block
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (1234500 + 700, 1234500 + 300, Uint128::from(1000000u128)),
+        label: None,
+        curve: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -667,6 +932,8 @@ fn test_add_reward_schedule() {
     env.block.height -= 2500;
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (12345 + 50, 1234500 + 150, Uint128::from(1000000u128)),
+        label: None,
+        curve: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -684,6 +951,8 @@ fn test_add_reward_schedule() {
 
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (12345 + 199, 1234500 + 2000, Uint128::from(1000000u128)),
+        label: None,
+        curve: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -701,6 +970,8 @@ fn test_add_reward_schedule() {
 
     let msg = ExecuteMsg::AddReward {
         reward_schedule: (12345 + 175, 1234500 + 185, Uint128::from(1000000u128)),
+        label: None,
+        curve: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -723,10 +994,26 @@ fn test_add_reward_schedule() {
             12345 + 201 + 500,
             Uint128::from(10000000000u128),
         ),
+        label: Some("season 2".to_string()),
+        curve: None,
     };
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        from_binary::<AddRewardResponse>(&res.data.unwrap()).unwrap(),
+        AddRewardResponse { schedule_id: 2 }
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_reward_schedule"),
+            attr("schedule_id", "2"),
+            attr("starting_block", "12546"),
+            attr("end_block", "13046"),
+            attr("total_emission", "10000000000"),
+        ]
+    );
 
     let config = from_binary::<ConfigResponse>(
         &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap(),
@@ -736,128 +1023,3738 @@ fn test_add_reward_schedule() {
     assert_eq!(
         config.distribution_schedule,
         vec![
-            (12345, 12345 + 100, Uint128::from(1000000u128)),
-            (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
-            (
-                12345 + 201,
-                12345 + 201 + 500,
-                Uint128::from(10000000000u128)
-            ),
+            RewardSchedule {
+                id: 0,
+                start: 12345,
+                end: 12345 + 100,
+                amount: Uint128::from(1000000u128),
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+            RewardSchedule {
+                id: 1,
+                start: 12345 + 100,
+                end: 12345 + 200,
+                amount: Uint128::from(10000000u128),
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+            RewardSchedule {
+                id: 2,
+                start: 12345 + 201,
+                end: 12345 + 201 + 500,
+                amount: Uint128::from(10000000000u128),
+                label: Some("season 2".to_string()),
+                curve: EmissionCurve::Linear,
+            },
         ]
     );
 }
-<<<<<<< HEAD
-=======
 
 #[test]
-fn test_modify_reward_schedule() {
+fn test_emission_curves() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
         xdefi_token: "reward0000".to_string(),
-        staking_token: "staking0000".to_string(),
-        distribution_schedule: vec![
-            (12345, 12345 + 100, Uint128::from(1000000u128)),
-            (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
-        ],
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![],
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    // query state after initialization of contract
-    let res = query(
-        deps.as_ref(),
-        mock_env(),
-        QueryMsg::State { block_height: None },
-    )
-    .unwrap();
-    let state: StateResponse = from_binary(&res).unwrap();
-    assert_eq!(
-        deps.api.addr_canonicalize("addr0000").unwrap(),
-        state.owner_address
-    );
-
-    let msg = ExecuteMsg::ModifyReward {
-        new_emission: Uint128::from(2000000u128),
-        index_schedule: 1,
-    };
-
-    let mut env = mock_env();
-
-    //we try to change owner with not authorized address : has to fail
-    let info = mock_info("notgov0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
-    match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
-        _ => panic!("Must return unauthorized error"),
-    }
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    //we try to modify a passed reward schedule
-    env.block.height += 2500;
+    // invalid curves
     let info = mock_info("addr0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
-
-    match res {
-        Err(StdError::GenericErr { msg, .. }) => {
-            assert_eq!(msg, "cannot modify a schedule that was passed already")
+    for (curve, error) in [
+        (
+            EmissionCurve::Step {
+                interval: 0,
+                ratio: Decimal::percent(50),
+            },
+            "Step interval has to be greater than 0",
+        ),
+        (
+            EmissionCurve::Step {
+                interval: 10,
+                ratio: Decimal::one(),
+            },
+            "Decay ratio has to be between 0 and 1",
+        ),
+        (
+            EmissionCurve::ExponentialDecay {
+                ratio: Decimal::zero(),
+            },
+            "Decay ratio has to be between 0 and 1",
+        ),
+    ] {
+        let msg = ExecuteMsg::AddReward {
+            reward_schedule: (12346, 12346 + 30, Uint128::from(7000u128)),
+            label: None,
+            curve: Some(curve),
+        };
+        match execute(deps.as_mut(), mock_env(), info.clone(), msg) {
+            Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, error),
+            _ => panic!("Must return : {}", error),
         }
-        _ => panic!("Must return : cannot modify a schedule that was passed already"),
     }
-    env.block.height -= 2500;
 
-    //we try to modify with an outlier index value
-    let msg = ExecuteMsg::ModifyReward {
-        new_emission: Uint128::from(2000000u128),
-        index_schedule: 100,
+    // emission halved every 10 blocks: 4000, 2000 then 1000
+    let msg = ExecuteMsg::AddReward {
+        reward_schedule: (12346, 12346 + 30, Uint128::from(7000u128)),
+        label: None,
+        curve: Some(EmissionCurve::Step {
+            interval: 10,
+            ratio: Decimal::percent(50),
+        }),
     };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-    let info = mock_info("addr0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    // emission halved every block: 4000, 2000 then 1000
+    let msg = ExecuteMsg::AddReward {
+        reward_schedule: (12400, 12400 + 3, Uint128::from(7000u128)),
+        label: None,
+        curve: Some(EmissionCurve::ExponentialDecay {
+            ratio: Decimal::percent(50),
+        }),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    match res {
-        Err(StdError::GenericErr { msg, .. }) => {
-            assert_eq!(msg, "index out of schedule array range")
-        }
-        _ => panic!("Must return : index out of schedule array range"),
+    let pending_reward_at = |deps: &OwnedDeps<_, _, _>, block_height: u64| {
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: Some(block_height),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .pending_reward
+    };
+
+    assert_eq!(
+        pending_reward_at(&deps, 12346 + 10),
+        Uint128::from(4000u128)
+    );
+    assert_eq!(
+        pending_reward_at(&deps, 12346 + 20),
+        Uint128::from(6000u128)
+    );
+    // half of the last interval
+    assert_eq!(
+        pending_reward_at(&deps, 12346 + 25),
+        Uint128::from(6500u128)
+    );
+    assert_eq!(
+        pending_reward_at(&deps, 12346 + 30),
+        Uint128::from(7000u128)
+    );
+
+    assert_eq!(
+        pending_reward_at(&deps, 12400 + 1),
+        Uint128::from(11000u128)
+    );
+    assert_eq!(
+        pending_reward_at(&deps, 12400 + 2),
+        Uint128::from(13000u128)
+    );
+    assert_eq!(
+        pending_reward_at(&deps, 12400 + 3),
+        Uint128::from(14000u128)
+    );
+
+    // distributing block by block gives the same amount as at once
+    let mut env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    for _ in 0..31 {
+        env.block.height += 1;
+        let msg = ExecuteMsg::Withdraw { pool_id: None };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
     }
+    let state = from_binary::<StateResponse>(
+        &query(deps.as_ref(), env, QueryMsg::State { block_height: None }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(7000u128));
+}
 
-    //we try to nullify a reward value
-    let msg = ExecuteMsg::ModifyReward {
-        new_emission: Uint128::from(0u128),
-        index_schedule: 1,
+#[test]
+fn test_notify_reward_amount() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![],
     };
 
     let info = mock_info("addr0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    match res {
-        Err(StdError::GenericErr { msg, .. }) => {
-            assert_eq!(msg, "Reward has to be greater than 0")
-        }
-        _ => panic!("Must return : Reward has to be greater than 0"),
-    }
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    //successful attempt
-    let msg = ExecuteMsg::ModifyReward {
-        new_emission: Uint128::from(20000000u128),
-        index_schedule: 1,
+    // the owner sends the rewards along
+    let notify_msg = |sender: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::NotifyRewardAmount {}).unwrap(),
+        })
     };
+    let token_info = mock_info("reward0000", &[]);
+    let msg = notify_msg("addr0000", 1000);
+    match execute(deps.as_mut(), env.clone(), token_info.clone(), msg.clone()) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Rewards duration is not set"),
+        _ => panic!("Must return : Rewards duration is not set"),
+    }
+
+    // only the owner funds the periods, with the reward token
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        token_info.clone(),
+        notify_msg("notgov0000", 1000),
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("staking0000", &[]),
+        msg.clone(),
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
 
     let info = mock_info("addr0000", &[]);
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
 
-    let config = from_binary::<ConfigResponse>(
-        &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap(),
+    let duration_msg = ExecuteMsg::SetRewardsDuration {
+        rewards_duration: 100,
+    };
+    match execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("notgov0000", &[]),
+        duration_msg.clone(),
+    ) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        duration_msg.clone(),
     )
     .unwrap();
+
+    // 1000 rewards over 100 blocks
+    let res = execute(deps.as_mut(), env.clone(), token_info.clone(), msg).unwrap();
     assert_eq!(
-        config.distribution_schedule,
-        vec![
-            (12345, 12345 + 100, Uint128::from(1000000u128)),
-            (12345 + 100, 12345 + 200, Uint128::from(20000000u128)),
-        ]
+        from_binary::<AddRewardResponse>(&res.data.unwrap()).unwrap(),
+        AddRewardResponse { schedule_id: 0 }
+    );
+    assert_eq!(
+        from_binary::<PeriodFinishResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::PeriodFinish {}).unwrap()
+        )
+        .unwrap(),
+        PeriodFinishResponse {
+            period_finish: 12345 + 100,
+            rewards_duration: 100,
+            reward_rate: Decimal::from_ratio(10u128, 1u128),
+        }
+    );
+
+    // the duration cannot change during a period
+    match execute(deps.as_mut(), env.clone(), info.clone(), duration_msg.clone()) {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "Previous rewards period must be complete before changing the duration for the new period"
+        ),
+        _ => panic!("Must return : Previous rewards period must be complete"),
+    }
+
+    // 50 blocks passed, the 500 rewards left roll over with the new 1000
+    env.block.height += 50;
+    let msg = notify_msg("addr0000", 1000);
+    let res = execute(deps.as_mut(), env.clone(), token_info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "notify_reward_amount"),
+            attr("schedule_id", "1"),
+            attr("amount", "1000"),
+            attr("leftover", "500"),
+            attr("period_finish", "12495"),
+        ]
+    );
+    assert_eq!(
+        from_binary::<PeriodFinishResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::PeriodFinish {}).unwrap()
+        )
+        .unwrap(),
+        PeriodFinishResponse {
+            period_finish: 12395 + 100,
+            rewards_duration: 100,
+            reward_rate: Decimal::from_ratio(15u128, 1u128),
+        }
+    );
+
+    // fixed schedules can overlap the rolling period
+    let msg = ExecuteMsg::AddReward {
+        reward_schedule: (12400, 12410, Uint128::from(100u128)),
+        label: None,
+        curve: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // 500 of the first period, 1500 of the second and 100 of the fixed schedule
+    env.block.height += 100;
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let state = from_binary::<StateResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::State { block_height: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(2100u128));
+
+    assert_eq!(
+        from_binary::<PeriodFinishResponse>(
+            &query(deps.as_ref(), env.clone(), QueryMsg::PeriodFinish {}).unwrap()
+        )
+        .unwrap(),
+        PeriodFinishResponse {
+            period_finish: 12495,
+            rewards_duration: 100,
+            reward_rate: Decimal::zero(),
+        }
+    );
+    let _res = execute(deps.as_mut(), env, info, duration_msg).unwrap();
+}
+
+#[test]
+fn test_modify_reward_schedule() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![
+            (12345, 12345 + 100, Uint128::from(1000000u128)),
+            (12345 + 100, 12345 + 200, Uint128::from(10000000u128)),
+        ],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // query state after initialization of contract
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::State { block_height: None },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        deps.api.addr_canonicalize("addr0000").unwrap(),
+        state.owner_address
+    );
+
+    let msg = ExecuteMsg::ModifyReward {
+        new_emission: Uint128::from(2000000u128),
+        schedule_id: 1,
+    };
+
+    let mut env = mock_env();
+
+    //we try to change owner with not authorized address : has to fail
+    let info = mock_info("notgov0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    //we try to modify a passed reward schedule
+    env.block.height += 2500;
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot modify a schedule that was passed already")
+        }
+        _ => panic!("Must return : cannot modify a schedule that was passed already"),
+    }
+    env.block.height -= 2500;
+
+    //we try to modify with an outlier index value
+    let msg = ExecuteMsg::ModifyReward {
+        new_emission: Uint128::from(2000000u128),
+        schedule_id: 100,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Reward schedule not found")
+        }
+        _ => panic!("Must return : Reward schedule not found"),
+    }
+
+    //we try to nullify a reward value
+    let msg = ExecuteMsg::ModifyReward {
+        new_emission: Uint128::from(0u128),
+        schedule_id: 1,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Reward has to be greater than 0")
+        }
+        _ => panic!("Must return : Reward has to be greater than 0"),
+    }
+
+    //successful attempt
+    let msg = ExecuteMsg::ModifyReward {
+        new_emission: Uint128::from(20000000u128),
+        schedule_id: 1,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let config = from_binary::<ConfigResponse>(
+        &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        config.distribution_schedule,
+        vec![
+            RewardSchedule {
+                id: 0,
+                start: 12345,
+                end: 12345 + 100,
+                amount: Uint128::from(1000000u128),
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+            RewardSchedule {
+                id: 1,
+                start: 12345 + 100,
+                end: 12345 + 200,
+                amount: Uint128::from(20000000u128),
+                label: None,
+                curve: EmissionCurve::Linear,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_historical_queries() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+    // bond 100 more tokens after 10 blocks
+    env.block.height += 10;
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // unbond everything after 10 more blocks
+    env.block.height += 10;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(200u128),
+        claim_rewards: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // query a height between the first and the second bond, past heights
+    // carry the emission forward from the values stored as of that height
+    let pool = from_binary::<PoolResponse>(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Pool {
+                pool_id: 0,
+                block_height: Some(12345 + 5),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool.total_bond_amount, Uint128::from(100u128));
+    assert_eq!(
+        pool.global_reward_index,
+        Decimal::from_ratio(500u128, 1u128)
+    );
+    assert_eq!(pool.last_distributed, 12345 + 5);
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: Some(12345 + 5),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::from_ratio(500u128, 1u128),
+            pending_reward: Uint128::from(50000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
+            bond_amount: Uint128::from(100u128),
+        }
+    );
+
+    // query a height between the second bond and the unbond
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: Some(12345 + 15),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::from_ratio(1250u128, 1u128),
+            pending_reward: Uint128::from(150000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
+            bond_amount: Uint128::from(200u128),
+        }
+    );
+
+    // a staker without any checkpoint has an empty position
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0001".to_string(),
+                    pool_id: None,
+                    block_height: Some(12345 + 5),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0001".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::from_ratio(500u128, 1u128),
+            pending_reward: Uint128::zero(),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
+            bond_amount: Uint128::zero(),
+        }
+    );
+
+    // later updates leave the past checkpoints untouched
+    let mut later_env = env.clone();
+    later_env.block.height += 10;
+    let msg = ExecuteMsg::ChangeOwner {
+        new_owner_address: "addr0001".to_string(),
+    };
+    let _res = execute(
+        deps.as_mut(),
+        later_env.clone(),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap();
+    let state = from_binary::<StateResponse>(
+        &query(
+            deps.as_ref(),
+            later_env,
+            QueryMsg::State {
+                block_height: Some(12345 + 20),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        state.owner_address,
+        deps.api.addr_canonicalize("addr0000").unwrap()
+    );
+
+    // heights before instantiation are rejected
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State {
+            block_height: Some(12344),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(
+                msg,
+                "block height is before the first stored state checkpoint"
+            )
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: Some(12344),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(
+                msg,
+                "block height is before the first stored state checkpoint"
+            )
+        }
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_checkpoint_retention() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+    let info = mock_info("staking0000", &[]);
+    env.block.height += 10;
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        bond_msg("addr0000", 100),
+    )
+    .unwrap();
+
+    // the first checkpoints fall out of the retention window
+    env.block.height += CHECKPOINT_RETENTION + 10;
+    let _res = execute(deps.as_mut(), env.clone(), info, bond_msg("addr0000", 100)).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State {
+            block_height: Some(12345 + 5),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "block height is older than the checkpoint retention")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // the latest checkpoint before the window still answers the heights within it
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: Some(12345 + 25),
+        },
+    )
+    .unwrap();
+    let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(staker_info.bond_amount, Uint128::from(200u128));
+
+    // while the older ones are pruned
+    let prefix = to_length_prefixed(b"state_checkpoint");
+    let checkpoints = deps
+        .storage
+        .range(Some(&prefix), None, Order::Ascending)
+        .take_while(|(k, _)| k.starts_with(&prefix))
+        .count();
+    assert_eq!(checkpoints, 2);
+}
+
+#[test]
+fn test_unbond_with_claim_rewards() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // 10 blocks passed
+    // 100,000 rewards distributed
+    env.block.height += 10;
+
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(40u128),
+        claim_rewards: Some(true),
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(40u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(100000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::from_ratio(1000u128, 1u128),
+            pending_reward: Uint128::zero(),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::from(100000u128),
+            bond_amount: Uint128::from(60u128),
+        }
+    );
+}
+
+#[test]
+fn test_exit() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    // nothing to exit
+    let info = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Exit { pool_id: None },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Nothing to exit"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // 10 blocks passed
+    // 100,000 rewards distributed
+    env.block.height += 10;
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env, info, ExecuteMsg::Exit { pool_id: None }).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(100000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "exit"),
+            attr("pool_id", "0"),
+            attr("owner", "addr0000"),
+            attr("amount", "100"),
+            attr("reward_amount", "100000"),
+        ]
+    );
+
+    // staker info is removed and the pool is empty, the claimed rewards are kept
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::zero(),
+            pending_reward: Uint128::zero(),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::from(100000u128),
+            bond_amount: Uint128::zero(),
+        }
+    );
+    let pool = from_binary::<PoolResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pool {
+                pool_id: 0,
+                block_height: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool.total_bond_amount, Uint128::zero());
+}
+
+#[test]
+fn test_withdraw_to_and_unbond_to() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    // no rewards yet
+    let stake_msg = to_binary(&GovCw20HookMsg::StakeVotingTokens {}).unwrap();
+    let msg = ExecuteMsg::WithdrawTo {
+        pool_id: None,
+        contract: "gov0000".to_string(),
+        msg: stake_msg.clone(),
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No rewards to withdraw"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // 10 blocks passed
+    // 100,000 rewards distributed
+    env.block.height += 10;
+
+    // hook messages which would credit the staking contract are rejected
+    let msg = ExecuteMsg::WithdrawTo {
+        pool_id: None,
+        contract: "gov0000".to_string(),
+        msg: to_binary(&GovCw20HookMsg::CreatePoll {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            link: None,
+            execute_msgs: None,
+        })
+        .unwrap(),
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
+            msg,
+            "hook message has to bond or stake on behalf of the staker"
+        ),
+        _ => panic!("Must return generic error"),
+    }
+
+    // restake rewards into gov, on behalf of the staker
+    let msg = ExecuteMsg::WithdrawTo {
+        pool_id: None,
+        contract: "gov0000".to_string(),
+        msg: stake_msg,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "gov0000".to_string(),
+                amount: Uint128::from(100000u128),
+                msg: to_binary(&GovCw20HookMsg::StakeVotingTokensFor {
+                    staker: "addr0000".to_string(),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "withdraw_to"),
+            attr("pool_id", "0"),
+            attr("owner", "addr0000"),
+            attr("amount", "100000"),
+            attr("contract", "gov0000"),
+        ]
+    );
+
+    // compound the rewards into a pool staking the reward token
+    let msg = ExecuteMsg::AddPool {
+        staking_token: AssetInfo::Token {
+            contract_addr: "reward0000".to_string(),
+        },
+        alloc_point: 1,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // 10 blocks passed, 50,000 rewards distributed to the default pool
+    env.block.height += 10;
+    let msg = ExecuteMsg::WithdrawTo {
+        pool_id: None,
+        contract: MOCK_CONTRACT_ADDR.to_string(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: Some(1),
+            staker: None,
+        })
+        .unwrap(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let bond_msg = to_binary(&Cw20HookMsg::Bond {
+        pool_id: Some(1),
+        staker: Some("addr0000".to_string()),
+    })
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(50000u128),
+                msg: bond_msg.clone(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // the reward token delivers the send, the user ends up holding the stake
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: MOCK_CONTRACT_ADDR.to_string(),
+        amount: Uint128::from(50000u128),
+        msg: bond_msg,
+    });
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        msg,
+    )
+    .unwrap();
+    for (staker, bond_amount) in [("addr0000", 50000u128), (MOCK_CONTRACT_ADDR, 0u128)].iter() {
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::StakerInfo {
+                staker: staker.to_string(),
+                pool_id: Some(1),
+                block_height: None,
+            },
+        )
+        .unwrap();
+        let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+        assert_eq!(staker_info.bond_amount, Uint128::from(*bond_amount));
+    }
+
+    // move the staking tokens into another staking contract
+    let msg = ExecuteMsg::UnbondTo {
+        pool_id: None,
+        amount: Uint128::from(100u128),
+        contract: "staking0001".to_string(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
+    };
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0001".to_string(),
+                amount: Uint128::from(100u128),
+                msg: to_binary(&Cw20HookMsg::Bond {
+                    pool_id: None,
+                    staker: Some("addr0000".to_string()),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn test_withdraw_to_another_deployment() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // a second deployment staking the reward token
+    let mut other_deps = mock_dependencies(&[]);
+    let mut other_env = mock_env();
+    other_env.contract.address = Addr::unchecked("staking_contract0001");
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "reward0000".to_string(),
+        },
+        distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+    };
+    let _res = instantiate(
+        other_deps.as_mut(),
+        other_env.clone(),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 10 blocks passed, 100,000 rewards withdrawn into the second deployment
+    env.block.height += 10;
+    let msg = ExecuteMsg::WithdrawTo {
+        pool_id: None,
+        contract: "staking_contract0001".to_string(),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
+    };
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    let bond_msg = to_binary(&Cw20HookMsg::Bond {
+        pool_id: None,
+        staker: Some("addr0000".to_string()),
+    })
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking_contract0001".to_string(),
+                amount: Uint128::from(100000u128),
+                msg: bond_msg.clone(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // the reward token delivers the send, sent by the first deployment
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: MOCK_CONTRACT_ADDR.to_string(),
+        amount: Uint128::from(100000u128),
+        msg: bond_msg,
+    });
+    let res = execute(
+        other_deps.as_mut(),
+        other_env.clone(),
+        mock_info("reward0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // the owner of the second deployment trusts the first one
+    let _res = execute(
+        other_deps.as_mut(),
+        other_env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateTrustedForwarders {
+            add: Some(vec![MOCK_CONTRACT_ADDR.to_string()]),
+            remove: None,
+        },
+    )
+    .unwrap();
+    let _res = execute(
+        other_deps.as_mut(),
+        other_env.clone(),
+        mock_info("reward0000", &[]),
+        msg,
+    )
+    .unwrap();
+    for (staker, bond_amount) in [("addr0000", 100000u128), (MOCK_CONTRACT_ADDR, 0u128)].iter() {
+        let res = query(
+            other_deps.as_ref(),
+            other_env.clone(),
+            QueryMsg::StakerInfo {
+                staker: staker.to_string(),
+                pool_id: None,
+                block_height: None,
+            },
+        )
+        .unwrap();
+        let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+        assert_eq!(staker_info.bond_amount, Uint128::from(*bond_amount));
+    }
+}
+
+#[test]
+fn test_native_staking_token() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+        distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // cw20 bond is not allowed on a native pool
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // wrong denom
+    let info = mock_info("addr0000", &[Coin::new(100u128, "uusd")]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondNative {
+            pool_id: None,
+            staker: None,
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "only uluna can be bonded"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // bond 100 uluna
+    let info = mock_info("addr0000", &[Coin::new(100u128, "uluna")]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondNative {
+            pool_id: None,
+            staker: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "bond"),
+            attr("pool_id", "0"),
+            attr("owner", "addr0000"),
+            attr("amount", "100"),
+        ]
+    );
+
+    // only the staking contract bonds on behalf of a staker
+    let info = mock_info("addr0000", &[Coin::new(100u128, "uluna")]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondNative {
+            pool_id: None,
+            staker: Some("addr0001".to_string()),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // unbond pays back with a bank send
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(60u128),
+        claim_rewards: None,
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin::new(60u128, "uluna")],
+        }))]
+    );
+
+    // unbonded coins are bonded elsewhere on behalf of the staker
+    let msg = ExecuteMsg::UnbondTo {
+        pool_id: None,
+        amount: Uint128::from(40u128),
+        contract: "staking0001".to_string(),
+        msg: to_binary(&ExecuteMsg::BondNative {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0001".to_string(),
+            msg: to_binary(&ExecuteMsg::BondNative {
+                pool_id: None,
+                staker: Some("addr0000".to_string()),
+            })
+            .unwrap(),
+            funds: vec![Coin::new(40u128, "uluna")],
+        }))]
+    );
+}
+
+#[test]
+fn test_bond_native_on_cw20_pool() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let info = mock_info("addr0000", &[Coin::new(100u128, "uluna")]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondNative {
+            pool_id: None,
+            staker: None,
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "staking token is not a native coin")
+        }
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_multiple_pools() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // 10 blocks passed
+    // 100,000 rewards distributed to the default pool
+    env.block.height += 10;
+
+    let msg = ExecuteMsg::AddPool {
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0001".to_string(),
+        },
+        alloc_point: 3,
+    };
+
+    // unauthorized attempt
+    let info = mock_info("notgov0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "add_pool"),
+            attr("pool_id", "1"),
+            attr("alloc_point", "3"),
+        ]
+    );
+
+    // a staking token gets a single pool
+    let msg = ExecuteMsg::AddPool {
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        alloc_point: 1,
+    };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "a pool already exists for the staking token")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // the total allocation point cannot overflow
+    let msg = ExecuteMsg::SetPool {
+        pool_id: 1,
+        alloc_point: u64::MAX,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    match res {
+        Err(StdError::Overflow { .. }) => {}
+        _ => panic!("Must return overflow error"),
+    }
+
+    // bonding the wrong token into the new pool fails
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0001".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: Some(1),
+            staker: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("staking0000", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // bond 100 tokens in the new pool
+    let info = mock_info("staking0001", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // 10 blocks passed
+    // 100,000 rewards split 1:3 between the pools
+    env.block.height += 10;
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: Some(env.block.height),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .pending_reward,
+        Uint128::from(125000u128)
+    );
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0001".to_string(),
+                    pool_id: Some(1),
+                    block_height: Some(env.block.height),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .pending_reward,
+        Uint128::from(75000u128)
+    );
+
+    // re-weight the new pool to an even split
+    let msg = ExecuteMsg::SetPool {
+        pool_id: 1,
+        alloc_point: 1,
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // unknown pool
+    let msg = ExecuteMsg::SetPool {
+        pool_id: 2,
+        alloc_point: 1,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "pool does not exist"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // 10 blocks passed
+    // 100,000 rewards split 1:1 between the pools
+    env.block.height += 10;
+    let info = mock_info("addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::Withdraw { pool_id: Some(1) },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(125000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: "addr0000".to_string(),
+                    pool_id: None,
+                    block_height: Some(env.block.height),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .pending_reward,
+        Uint128::from(175000u128)
+    );
+
+    let pools = from_binary::<PoolsResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        pools
+            .pools
+            .iter()
+            .map(|pool| (pool.pool_id, pool.alloc_point))
+            .collect::<Vec<(u64, u64)>>(),
+        vec![(0, 1), (1, 1)]
+    );
+
+    let state = from_binary::<StateResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::State { block_height: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_alloc_point, 2);
+    assert_eq!(state.pool_count, 2);
+}
+
+#[test]
+fn test_allowlist() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = ExecuteMsg::UpdateAllowlist {
+        enabled: Some(true),
+        add: Some(vec!["addr0001".to_string(), "addr0002".to_string()]),
+        remove: None,
+    };
+
+    // unauthorized attempt
+    let info = mock_info("addr0001", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let info = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateAllowlist {
+        enabled: None,
+        add: None,
+        remove: Some(vec!["addr0002".to_string()]),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        from_binary::<AllowlistResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Allowlist {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        AllowlistResponse {
+            enabled: true,
+            addresses: vec!["addr0001".to_string()],
+        }
+    );
+
+    // addresses off the allowlist cannot bond
+    let msg = bond_msg("addr0002", 100);
+    let info = mock_info("staking0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "sender is not allowlisted"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let msg = bond_msg("addr0001", 100);
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // everyone can bond once allowlist mode is disabled
+    let msg = ExecuteMsg::UpdateAllowlist {
+        enabled: Some(false),
+        add: None,
+        remove: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = bond_msg("addr0002", 100);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn test_freeze() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = bond_msg("addr0001", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Freeze {
+        staker: "addr0001".to_string(),
+    };
+
+    // unauthorized attempt
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "freeze"), attr("staker", "addr0001")]
+    );
+
+    assert_eq!(
+        from_binary::<FrozenStakersResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::FrozenStakers {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        FrozenStakersResponse {
+            stakers: vec!["addr0001".to_string()],
+        }
+    );
+
+    env.block.height += 10;
+    let info = mock_info("addr0001", &[]);
+    for msg in [
+        ExecuteMsg::Withdraw { pool_id: None },
+        ExecuteMsg::Unbond {
+            pool_id: None,
+            amount: Uint128::from(100u128),
+            claim_rewards: None,
+        },
+        ExecuteMsg::Exit { pool_id: None },
+    ] {
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
+        match res {
+            Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "staker is frozen"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    let msg = ExecuteMsg::Unfreeze {
+        staker: "addr0001".to_string(),
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // rewards kept accruing while frozen
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(100000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn test_bond_limits() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = ExecuteMsg::UpdateBondLimits {
+        pool_id: None,
+        max_total_bond: Some(Uint128::from(300u128)),
+        max_bond_per_staker: Some(Uint128::from(200u128)),
+        min_bond_amount: Some(Uint128::from(50u128)),
+    };
+
+    // unauthorized attempt
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "update_bond_limits"),
+            attr("pool_id", "0"),
+            attr("max_total_bond", "300"),
+            attr("max_bond_per_staker", "200"),
+            attr("min_bond_amount", "50"),
+        ]
+    );
+
+    let bond_msg = |sender: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                pool_id: None,
+                staker: None,
+            })
+            .unwrap(),
+        })
+    };
+    let info = mock_info("staking0000", &[]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0001", 20),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Bond amount is below the minimum of 50")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0001", 150),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0001", 100),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Bond exceeds the per staker cap of 200")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0002", 200),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Bond exceeds the pool cap of 300")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    assert_eq!(
+        from_binary::<CapacityResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Capacity {
+                    pool_id: None,
+                    staker: Some("addr0001".to_string()),
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        CapacityResponse {
+            pool_id: 0,
+            max_total_bond: Some(Uint128::from(300u128)),
+            max_bond_per_staker: Some(Uint128::from(200u128)),
+            min_bond_amount: Some(Uint128::from(50u128)),
+            total_bond_amount: Uint128::from(150u128),
+            remaining_capacity: Some(Uint128::from(150u128)),
+            filled_ratio: Some(Decimal::percent(50)),
+            staker_remaining_capacity: Some(Uint128::from(50u128)),
+        }
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), info, bond_msg("addr0002", 150)).unwrap();
+
+    let capacity = from_binary::<CapacityResponse>(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Capacity {
+                pool_id: None,
+                staker: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(capacity.remaining_capacity, Some(Uint128::zero()));
+    assert_eq!(capacity.filled_ratio, Some(Decimal::one()));
+    assert_eq!(capacity.staker_remaining_capacity, None);
+}
+
+#[test]
+fn test_staking_events() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![StakingEvent::Bond(BondEvent {
+            pool_id: 0,
+            staker: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            bond_amount: Uint128::from(100u128),
+            total_bond_amount: Uint128::from(100u128),
+            global_reward_index: Decimal::zero(),
+        })]
+    );
+
+    // 10 blocks passed
+    // 100,000 rewards distributed
+    env.block.height += 10;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(40u128),
+        claim_rewards: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![StakingEvent::Unbond(UnbondEvent {
+            pool_id: 0,
+            staker: "addr0000".to_string(),
+            amount: Uint128::from(40u128),
+            reward_amount: Uint128::zero(),
+            bond_amount: Uint128::from(60u128),
+            total_bond_amount: Uint128::from(60u128),
+            global_reward_index: Decimal::from_ratio(1000u128, 1u128),
+            contract: None,
+        })]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![StakingEvent::Withdraw(WithdrawEvent {
+            pool_id: 0,
+            staker: "addr0000".to_string(),
+            amount: Uint128::from(100000u128),
+            global_reward_index: Decimal::from_ratio(1000u128, 1u128),
+            contract: None,
+        })]
+    );
+}
+
+#[test]
+fn test_stake_changed_hooks() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = ExecuteMsg::AddHook {
+        addr: "gauge0000".to_string(),
+    };
+
+    // unauthorized attempt
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let owner = mock_info("addr0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), owner.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "hook already registered"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // fill up the registry
+    for i in 1..10 {
+        let msg = ExecuteMsg::AddHook {
+            addr: format!("hook{:04}", i),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    }
+    let msg = ExecuteMsg::AddHook {
+        addr: "hook0010".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot register more than 10 hooks")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    // keep a single hook
+    for i in 1..10 {
+        let msg = ExecuteMsg::RemoveHook {
+            addr: format!("hook{:04}", i),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+    }
+    assert_eq!(
+        from_binary::<HooksResponse>(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap()
+        )
+        .unwrap(),
+        HooksResponse {
+            hooks: vec!["gauge0000".to_string()],
+        }
+    );
+
+    let hook_msg = |old_bond: u128, new_bond: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "gauge0000".to_string(),
+            msg: to_binary(&StakeChangedHookMsg::StakeChangedHook {
+                pool_id: 0,
+                addr: "addr0001".to_string(),
+                old_bond: Uint128::from(old_bond),
+                new_bond: Uint128::from(new_bond),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    let msg = bond_msg("addr0001", 100);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![hook_msg(0, 100)]);
+
+    let info = mock_info("addr0001", &[]);
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(40u128),
+        claim_rewards: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[1], hook_msg(100, 60));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Exit { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.messages[1], hook_msg(60, 0));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner,
+        ExecuteMsg::MigrateStaking {
+            new_staking_contract: "newstaking0000".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages.last().unwrap(),
+        &SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "gauge0000".to_string(),
+            msg: to_binary(&StakeChangedHookMsg::StakingMigrated {
+                new_staking_contract: "newstaking0000".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+}
+
+#[test]
+fn test_reward_vesting() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    // unauthorized attempt
+    let msg = ExecuteMsg::SetRewardVesting { period: Some(100) };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let owner = mock_info("addr0000", &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner.clone(),
+        ExecuteMsg::SetRewardVesting { period: Some(0) },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Vesting period has to be greater than 0")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let _res = execute(deps.as_mut(), mock_env(), owner, msg).unwrap();
+
+    let msg = bond_msg("addr0001", 100);
+    let mut env = mock_env();
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 10 blocks passed
+    // 100,000 rewards move to the vesting ledger instead of being paid out
+    env.block.height += 10;
+    let info = mock_info("addr0001", &[]);
+
+    // rewards cannot vest in another contract
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::WithdrawTo {
+            pool_id: None,
+            contract: "contract0000".to_string(),
+            msg: to_binary(&GovCw20HookMsg::StakeVotingTokens {}).unwrap(),
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "vesting rewards cannot be sent to a contract")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert!(parse_events(&res.events)
+        .unwrap()
+        .contains(&StakingEvent::VestRewards(VestRewardsEvent {
+            staker: "addr0001".to_string(),
+            amount: Uint128::from(100000u128),
+            start_block: 12345 + 10,
+            end_block: 12345 + 110,
+        })));
+
+    // half of the vesting period passed
+    env.block.height += 50;
+    assert_eq!(
+        from_binary::<RewardVestingResponse>(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::RewardVesting {
+                    staker: "addr0001".to_string(),
+                    block_height: None,
+                },
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        RewardVestingResponse {
+            staker: "addr0001".to_string(),
+            vested: Uint128::from(50000u128),
+            unvested: Uint128::from(50000u128),
+            info: VestingInfo {
+                schedules: vec![(12345 + 10, 12345 + 110, Uint128::from(100000u128))],
+                last_claim_time: 12345 + 10,
+                cliff: None,
+                revocable: false,
+            },
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ClaimVested {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(50000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ClaimVested {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No vested rewards to claim"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // the rest unlocks at the end of the vesting period
+    env.block.height += 100;
+    let res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::ClaimVested {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(50000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let vesting = from_binary::<RewardVestingResponse>(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::RewardVesting {
+                staker: "addr0001".to_string(),
+                block_height: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(vesting.vested, Uint128::zero());
+    assert_eq!(vesting.unvested, Uint128::zero());
+    assert_eq!(vesting.info.schedules, vec![]);
+}
+
+#[test]
+fn test_distributor_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = ExecuteMsg::SetDistributor {
+        distributor_contract: Some("distributor0000".to_string()),
+    };
+
+    // unauthorized attempt
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.distributor_contract,
+        Some("distributor0000".to_string())
+    );
+
+    let msg = bond_msg("addr0001", 100);
+    let mut env = mock_env();
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 10 blocks passed
+    // 100,000 rewards are spent by the distributor
+    env.block.height += 10;
+    let info = mock_info("addr0001", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "distributor0000".to_string(),
+            msg: to_binary(&DistributorExecuteMsg::Spend {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(100000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // rewards sent to a contract pass through the staking contract
+    env.block.height += 10;
+    let hook_msg = to_binary(&GovCw20HookMsg::StakeVotingTokens {}).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::WithdrawTo {
+            pool_id: None,
+            contract: "gov0000".to_string(),
+            msg: hook_msg.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "distributor0000".to_string(),
+                msg: to_binary(&DistributorExecuteMsg::Spend {
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::from(100000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "reward0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "gov0000".to_string(),
+                    amount: Uint128::from(100000u128),
+                    msg: to_binary(&GovCw20HookMsg::StakeVotingTokensFor {
+                        staker: "addr0001".to_string(),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // revenue distributed at once is forwarded to the distributor paying it out
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "collector0000".to_string(),
+            amount: Uint128::from(500u128),
+            msg: to_binary(&Cw20HookMsg::DistributeNow { pool_id: None }).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "distributor0000".to_string(),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "distributor0000".to_string(),
+            msg: to_binary(&DistributorExecuteMsg::Spend {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // the remaining rewards stay in the distributor on migration
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::MigrateStaking {
+            new_staking_contract: "newstaking0000".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+}
+
+#[test]
+fn test_distribute_now() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let distribute_msg = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "collector0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::DistributeNow { pool_id: None }).unwrap(),
+        })
+    };
+    let bond_msg = |staker: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                pool_id: None,
+                staker: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    // only the reward token can be distributed
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        distribute_msg(1000),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // nothing is bonded yet, the amount is deferred to the first bond
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("reward0000", &[]),
+        distribute_msg(1000),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "distribute_now"),
+            attr("pool_id", "0"),
+            attr("amount", "1000"),
+            attr("deferred", "true"),
+        ]
+    );
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![StakingEvent::DistributeNow(DistributeNowEvent {
+            pool_id: 0,
+            asset: "reward0000".to_string(),
+            amount: Uint128::from(1000u128),
+            deferred: true,
+        })]
+    );
+
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0000", 100),
+    )
+    .unwrap();
+    let _res = execute(deps.as_mut(), mock_env(), info, bond_msg("addr0001", 300)).unwrap();
+
+    // 400 / 400 bonded is added to the reward index
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("reward0000", &[]),
+        distribute_msg(400),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "distribute_now"),
+            attr("pool_id", "0"),
+            attr("amount", "400"),
+            attr("deferred", "false"),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Pool {
+            pool_id: 0,
+            block_height: None,
+        },
+    )
+    .unwrap();
+    let pool: PoolResponse = from_binary(&res).unwrap();
+    assert_eq!(pool.global_reward_index, Decimal::from_ratio(11u128, 1u128));
+
+    let pending_reward = |staker: &str| {
+        from_binary::<StakerInfoResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    staker: staker.to_string(),
+                    pool_id: None,
+                    block_height: Some(mock_env().block.height),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .pending_reward
+    };
+    assert_eq!(pending_reward("addr0000"), Uint128::from(1100u128));
+    assert_eq!(pending_reward("addr0001"), Uint128::from(300u128));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(1100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // 1000 / 403 bonded credits 999, the rounding remainder is still owed
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        bond_msg("addr0002", 3),
+    )
+    .unwrap();
+    let accounting = |deps: Deps| {
+        let state: StateResponse =
+            from_binary(&query(deps, mock_env(), QueryMsg::State { block_height: None }).unwrap())
+                .unwrap();
+        let solvency: SolvencyResponse =
+            from_binary(&query(deps, mock_env(), QueryMsg::Solvency {}).unwrap()).unwrap();
+        (state.total_distributed, solvency.pending_rewards)
+    };
+    let (total_distributed, pending_rewards) = accounting(deps.as_ref());
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("reward0000", &[]),
+        distribute_msg(1000),
+    )
+    .unwrap();
+    assert_eq!(
+        accounting(deps.as_ref()),
+        (
+            total_distributed + Uint128::from(999u128),
+            pending_rewards + Uint128::from(1000u128)
+        )
+    );
+}
+
+#[test]
+fn test_distribute_native_now() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let distribute_msg = ExecuteMsg::DistributeNativeNow { pool_id: None };
+    let bond_msg = |staker: &str, amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: staker.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Bond {
+                pool_id: None,
+                staker: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("collector0000", &[]),
+        distribute_msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No coins to distribute"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // deferred until the first bond
+    let info = mock_info("collector0000", &[Coin::new(500u128, "uusd")]);
+    let res = execute(deps.as_mut(), mock_env(), info, distribute_msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "distribute_native_now"),
+            attr("pool_id", "0"),
+            attr("deferred", "true"),
+        ]
+    );
+
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        bond_msg("addr0000", 100),
+    )
+    .unwrap();
+
+    let info_collector = mock_info(
+        "collector0000",
+        &[Coin::new(200u128, "uusd"), Coin::new(100u128, "ukrw")],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info_collector,
+        distribute_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        parse_events(&res.events).unwrap(),
+        vec![
+            StakingEvent::DistributeNow(DistributeNowEvent {
+                pool_id: 0,
+                asset: "uusd".to_string(),
+                amount: Uint128::from(200u128),
+                deferred: false,
+            }),
+            StakingEvent::DistributeNow(DistributeNowEvent {
+                pool_id: 0,
+                asset: "ukrw".to_string(),
+                amount: Uint128::from(100u128),
+                deferred: false,
+            }),
+        ]
+    );
+
+    // the new staker does not share the coins distributed before its bond
+    let _res = execute(deps.as_mut(), mock_env(), info, bond_msg("addr0001", 100)).unwrap();
+    let info_collector = mock_info("collector0000", &[Coin::new(200u128, "uusd")]);
+    let _res = execute(deps.as_mut(), mock_env(), info_collector, distribute_msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: Some(mock_env().block.height),
+        },
+    )
+    .unwrap();
+    let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        staker_info.pending_native_rewards,
+        vec![Coin::new(800u128, "uusd"), Coin::new(100u128, "ukrw")]
+    );
+
+    // only native rewards are pending, so nothing is asked from the reward token
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Withdraw { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: vec![Coin::new(100u128, "uusd")],
+        }))]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Exit { pool_id: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(800u128, "uusd"), Coin::new(100u128, "ukrw")],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn test_staker_infos() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let info = mock_info("staking0000", &[]);
+    for (staker, amount) in [("addr0000", 100u128), ("addr0001", 300u128)].iter() {
+        let msg = bond_msg(staker, *amount);
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    let block_height = mock_env().block.height + 100;
+    let stakers = vec![
+        "addr0000".to_string(),
+        "addr0001".to_string(),
+        "addr0002".to_string(),
+    ];
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StakerInfos {
+            stakers: stakers.clone(),
+            pool_id: None,
+            block_height: Some(block_height),
+        },
+    )
+    .unwrap();
+    let res: StakerInfosResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        res.staker_infos,
+        vec![
+            StakerInfoResponse {
+                staker: "addr0000".to_string(),
+                pool_id: 0,
+                reward_index: Decimal::from_ratio(2500u128, 1u128),
+                bond_amount: Uint128::from(100u128),
+                pending_reward: Uint128::from(250000u128),
+                pending_native_rewards: vec![],
+                total_claimed: Uint128::zero(),
+            },
+            StakerInfoResponse {
+                staker: "addr0001".to_string(),
+                pool_id: 0,
+                reward_index: Decimal::from_ratio(2500u128, 1u128),
+                bond_amount: Uint128::from(300u128),
+                pending_reward: Uint128::from(750000u128),
+                pending_native_rewards: vec![],
+                total_claimed: Uint128::zero(),
+            },
+            StakerInfoResponse {
+                staker: "addr0002".to_string(),
+                pool_id: 0,
+                reward_index: Decimal::from_ratio(2500u128, 1u128),
+                bond_amount: Uint128::zero(),
+                pending_reward: Uint128::zero(),
+                pending_native_rewards: vec![],
+                total_claimed: Uint128::zero(),
+            },
+        ]
+    );
+
+    // the batch matches the single staker query
+    for (staker, staker_info) in stakers.iter().zip(res.staker_infos.iter()) {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::StakerInfo {
+                staker: staker.to_string(),
+                pool_id: None,
+                block_height: Some(block_height),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            &from_binary::<StakerInfoResponse>(&res).unwrap(),
+            staker_info
+        );
+    }
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::StakerInfos {
+            stakers: (0..MAX_STAKER_INFOS + 1)
+                .map(|i| format!("addr{:04}", i))
+                .collect(),
+            pool_id: None,
+            block_height: None,
+        },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "cannot query more than 50 stakers at once")
+        }
+        _ => panic!("Must return generic error"),
+    }
+}
+
+#[test]
+fn test_top_stakers() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let info = mock_info("staking0000", &[]);
+    for (staker, amount) in [
+        ("addr0000", 100u128),
+        ("addr0001", 300u128),
+        ("addr0002", 200u128),
+        ("addr0003", 200u128),
+    ]
+    .iter()
+    {
+        let msg = bond_msg(staker, *amount);
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    fn top_stakers_page(
+        deps: Deps,
+        start_after: Option<(Uint128, String)>,
+        limit: Option<u32>,
+    ) -> TopStakersResponse {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::TopStakers {
+                pool_id: None,
+                start_after,
+                limit,
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    fn top_stakers(deps: Deps, limit: Option<u32>) -> Vec<(String, u128)> {
+        top_stakers_page(deps, None, limit)
+            .stakers
+            .into_iter()
+            .map(|s| (s.staker, s.bond_amount.u128()))
+            .collect()
+    }
+
+    // ties are ordered by address
+    let page = top_stakers_page(deps.as_ref(), None, Some(2));
+    assert_eq!(
+        page.stakers
+            .iter()
+            .map(|s| (s.staker.as_str(), s.bond_amount.u128()))
+            .collect::<Vec<_>>(),
+        vec![("addr0001", 300), ("addr0003", 200)]
+    );
+    assert_eq!(
+        page.next_start_after,
+        Some((Uint128::from(200u128), "addr0003".to_string()))
+    );
+
+    // the cursor keeps its place when the last staker changes its bond in between
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(150u128),
+        claim_rewards: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0003", &[]), msg).unwrap();
+
+    let page = top_stakers_page(deps.as_ref(), page.next_start_after, Some(2));
+    assert_eq!(
+        page.stakers
+            .iter()
+            .map(|s| (s.staker.as_str(), s.bond_amount.u128()))
+            .collect::<Vec<_>>(),
+        vec![("addr0002", 200), ("addr0000", 100)]
+    );
+    let page = top_stakers_page(deps.as_ref(), page.next_start_after, Some(2));
+    assert_eq!(
+        page.stakers
+            .iter()
+            .map(|s| (s.staker.as_str(), s.bond_amount.u128()))
+            .collect::<Vec<_>>(),
+        vec![("addr0003", 50)]
+    );
+    let page = top_stakers_page(deps.as_ref(), page.next_start_after, Some(2));
+    assert!(page.stakers.is_empty());
+    assert_eq!(page.next_start_after, None);
+
+    // the index follows bond changes
+    let msg = ExecuteMsg::Unbond {
+        pool_id: None,
+        amount: Uint128::from(250u128),
+        claim_rewards: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0001", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Exit { pool_id: None };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    assert_eq!(
+        top_stakers(deps.as_ref(), None),
+        vec![
+            ("addr0002".to_string(), 200),
+            ("addr0003".to_string(), 50),
+            ("addr0001".to_string(), 50),
+        ]
+    );
+}
+
+#[test]
+fn test_reward_accounting() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    let msg = bond_msg("addr0000", 100);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // half of the schedule is emitted and withdrawn
+    let mut env = mock_env();
+    env.block.height += 50;
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // revenue distributed at once counts as distributed too
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "collector0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::DistributeNow { pool_id: None }).unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { block_height: None },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(501000u128));
+    assert_eq!(state.total_claimed, Uint128::from(500000u128));
+
+    // the rest of the schedule
+    env.block.height += 50;
+    let msg = ExecuteMsg::Exit { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::State { block_height: None },
+    )
+    .unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(1001000u128));
+    assert_eq!(state.total_claimed, Uint128::from(1001000u128));
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: None,
+        },
+    )
+    .unwrap();
+    let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(staker_info.total_claimed, Uint128::from(1001000u128));
+}
+
+#[test]
+fn test_reward_accounting_uncredited() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate_single_schedule(&mut deps);
+
+    // the second pool gets half of the emission, nobody ever bonds in it
+    let msg = ExecuteMsg::AddPool {
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0001".to_string(),
+        },
+        alloc_point: 1,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = bond_msg("addr0000", 3);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    // 500,000 rewards are emitted to each pool,
+    // the bonded one credits them down to the rounding dust
+    let mut env = mock_env();
+    env.block.height += 100;
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let state: StateResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::State { block_height: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(499999u128));
+    assert_eq!(state.total_claimed, Uint128::from(499999u128));
+
+    // the share of the pool not synced yet is still owed
+    let solvency: SolvencyResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap()).unwrap();
+    assert_eq!(solvency.pending_rewards, Uint128::from(500000u128));
+
+    // once synced, the share of the empty pool is not distributed to anybody
+    let msg = ExecuteMsg::SetPool {
+        pool_id: 1,
+        alloc_point: 1,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let state: StateResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::State { block_height: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.total_distributed, Uint128::from(499999u128));
+
+    let solvency: SolvencyResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::Solvency {}).unwrap()).unwrap();
+    assert_eq!(solvency.pending_rewards, Uint128::zero());
+}
+
+#[test]
+fn test_solvency() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[
+        (
+            &"reward0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+        ),
+        (
+            &"staking0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+        ),
+    ]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // 50 blocks passed, half of the schedule is emitted
+    env.block.height += 50;
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        solvency,
+        SolvencyResponse {
+            staking_tokens: vec![StakingTokenSolvency {
+                staking_token: AssetInfo::Token {
+                    contract_addr: "staking0000".to_string(),
+                },
+                balance: Uint128::from(100u128),
+                liability: Uint128::from(100u128),
+                shortfall: Uint128::zero(),
+            }],
+            reward_balance: Uint128::from(1000000u128),
+            pending_rewards: Uint128::from(500000u128),
+            unemitted_rewards: Uint128::from(500000u128),
+            reward_liability: Uint128::from(1000000u128),
+            reward_shortfall: Uint128::zero(),
+            emission_halted: false,
+        }
     );
-}
 
->>>>>>> 073d9944f18de4cf7de2ec30704c0ec444ea6c26
+    // the contract only holds part of the schedule and of the bonded tokens
+    deps.querier.with_token_balances(&[
+        (
+            &"reward0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(600000u128))],
+        ),
+        (
+            &"staking0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(40u128))],
+        ),
+    ]);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(solvency.staking_tokens[0].shortfall, Uint128::from(60u128));
+    assert_eq!(solvency.reward_liability, Uint128::from(1000000u128));
+    assert_eq!(solvency.reward_shortfall, Uint128::from(400000u128));
+
+    // reward tokens bonded in a pool are owed to their stakers, not to the rewards
+    let msg = ExecuteMsg::AddPool {
+        staking_token: AssetInfo::Token {
+            contract_addr: "reward0000".to_string(),
+        },
+        alloc_point: 1,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0001".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: Some(1),
+            staker: None,
+        })
+        .unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("reward0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(solvency.reward_balance, Uint128::from(599000u128));
+
+    let msg = ExecuteMsg::Unbond {
+        pool_id: Some(1),
+        amount: Uint128::from(400u128),
+        claim_rewards: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0001", &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), env, QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(solvency.reward_balance, Uint128::from(599400u128));
+}
+
+#[test]
+fn test_insolvency_circuit_breaker() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(600000u128))],
+    )]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // 50 blocks passed, 500,000 emitted and 500,000 still to emit
+    // against a 600,000 balance, the withdrawal halts the emission
+    env.block.height += 50;
+    let info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(500000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert!(parse_events(&res.events)
+        .unwrap()
+        .contains(&StakingEvent::InsolvencyAlert(InsolvencyAlertEvent {
+            reward_balance: Uint128::from(600000u128),
+            reward_liability: Uint128::from(1000000u128),
+            shortfall: Uint128::from(400000u128),
+        })));
+
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100000u128))],
+    )]);
+
+    // nothing is emitted while halted, the rest of the schedule is still owed
+    env.block.height += 30;
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert!(solvency.emission_halted);
+    assert_eq!(solvency.pending_rewards, Uint128::zero());
+    assert_eq!(solvency.unemitted_rewards, Uint128::from(500000u128));
+    assert_eq!(solvency.reward_shortfall, Uint128::from(400000u128));
+
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    // only the owner resumes the emission
+    let msg = ExecuteMsg::ResumeEmission {};
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("Must return unauthorized error"),
+    }
+
+    // the balance has to cover the rewards scheduled while halted
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "Reward balance does not cover the rewards owed")
+        }
+        _ => panic!("Must return generic error"),
+    }
+
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(500000u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "resume_emission"),
+            attr("last_distributed", "12425"),
+            attr("backlog_amount", "300000"),
+        ]
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "Emission is not halted"),
+        _ => panic!("Must return generic error"),
+    }
+
+    // the 30 halted blocks are emitted at once, then the last 20 blocks of the schedule
+    env.block.height += 20;
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: Some(env.block.height),
+        },
+    )
+    .unwrap();
+    let staker_info: StakerInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(staker_info.pending_reward, Uint128::from(500000u128));
+}
+
+#[test]
+fn test_migrate_staking_while_halted() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[(
+        &"reward0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(600000u128))],
+    )]);
+
+    let mut env = instantiate_and_bond(&mut deps);
+
+    // the withdrawal halts the emission half way through the schedule
+    env.block.height += 50;
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // the rewards scheduled while halted were never emitted, they move along
+    env.block.height += 30;
+    let msg = ExecuteMsg::MigrateStaking {
+        new_staking_contract: "newstaking0000".to_string(),
+    };
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "reward0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "newstaking0000".to_string(),
+                amount: Uint128::from(500000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+// counts the storage reads, standing in for the gas used by an execution
+#[derive(Default)]
+struct CountingStorage {
+    storage: MockStorage,
+    reads: Cell<u64>,
+}
+
+impl Storage for CountingStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.reads.set(self.reads.get() + 1);
+        self.storage.get(key)
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Pair> + 'a> {
+        Box::new(
+            self.storage
+                .range(start, end, order)
+                .inspect(move |_| self.reads.set(self.reads.get() + 1)),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.storage.remove(key)
+    }
+}
+
+// a staker bonded through the given number of finished 10 blocks schedules of 1000 rewards,
+// withdrawing them all archives the schedules
+fn withdraw_after_schedules(
+    finished_schedules: u64,
+) -> (OwnedDeps<CountingStorage, MockApi, WasmMockQuerier>, Env) {
+    let mut deps = OwnedDeps {
+        storage: CountingStorage::default(),
+        api: MockApi::default(),
+        querier: mock_dependencies(&[]).querier,
+    };
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // bond 100 tokens
+    let msg = bond_msg("addr0000", 100);
+    let info = mock_info("staking0000", &[]);
+    let mut env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    for i in 0..finished_schedules {
+        let start = env.block.height + 1 + i * 20;
+        let msg = ExecuteMsg::AddReward {
+            reward_schedule: (start, start + 10, Uint128::from(1000u128)),
+            label: None,
+            curve: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    }
+
+    env.block.height += finished_schedules * 20 + 10;
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("amount", (finished_schedules * 1000).to_string())));
+
+    (deps, env)
+}
+
+#[test]
+fn test_reward_schedules_gas() {
+    let (mut deps, mut env) = withdraw_after_schedules(300);
+
+    // the finished schedules are archived
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.distribution_schedule, vec![]);
+
+    // a withdrawal reads as much storage after hundreds of schedules as after one
+    env.block.height += 10;
+    deps.storage.reads.set(0);
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+    let reads = deps.storage.reads.get();
+
+    let (mut deps, mut env) = withdraw_after_schedules(1);
+    env.block.height += 10;
+    deps.storage.reads.set(0);
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(reads, deps.storage.reads.get());
+}
+
+// a staker bonded in the default pool next to the given number of other pools
+fn withdraw_next_to_pools(pools: u64) -> u64 {
+    let mut deps = OwnedDeps {
+        storage: CountingStorage::default(),
+        api: MockApi::default(),
+        querier: mock_dependencies(&[]).querier,
+    };
+
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    for i in 1..=pools {
+        let msg = ExecuteMsg::AddPool {
+            staking_token: AssetInfo::Token {
+                contract_addr: format!("staking{:04}", i),
+            },
+            alloc_point: 1,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    }
+
+    let msg = bond_msg("addr0000", 100);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("staking0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.height += 10;
+    deps.storage.reads.set(0);
+    let msg = ExecuteMsg::Withdraw { pool_id: None };
+    let _res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    deps.storage.reads.get()
+}
+
+#[test]
+fn test_solvency_check_gas() {
+    // the solvency check of a withdrawal reads no other pool
+    assert_eq!(withdraw_next_to_pools(30), withdraw_next_to_pools(0));
+}
+
+#[test]
+fn test_migrate_single_pool() {
+    let mut deps = mock_dependencies(&[]);
+
+    // layout of the contracts deployed before the pools
+    let api = deps.api;
+    singleton(&mut deps.storage, b"config")
+        .save(&LegacyConfig {
+            xdefi_token: api.addr_canonicalize("reward0000").unwrap(),
+            staking_token: api.addr_canonicalize("staking0000").unwrap(),
+            distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+        })
+        .unwrap();
+    singleton(&mut deps.storage, b"state")
+        .save(&LegacyState {
+            last_distributed: 12345 + 10,
+            total_bond_amount: Uint128::from(100u128),
+            global_reward_index: Decimal::from_ratio(1000u128, 1u128),
+            owner_address: api.addr_canonicalize("addr0000").unwrap(),
+        })
+        .unwrap();
+    for (staker, reward_index, bond_amount, pending_reward) in [
+        ("addr0000", 500u128, 60u128, 10000u128),
+        ("addr0001", 1000, 40, 0),
+    ]
+    .iter()
+    {
+        Bucket::new(&mut deps.storage, b"reward")
+            .save(
+                api.addr_canonicalize(staker).unwrap().as_slice(),
+                &LegacyStakerInfo {
+                    reward_index: Decimal::from_ratio(*reward_index, 1u128),
+                    bond_amount: Uint128::from(*bond_amount),
+                    pending_reward: Uint128::from(*pending_reward),
+                    moved_to_pool: false,
+                },
+            )
+            .unwrap();
+    }
+
+    let mut env = mock_env();
+    env.block.height += 20;
+    let _res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.xdefi_token, "reward0000");
+    assert_eq!(config.staking_token, "staking0000");
+    assert_eq!(
+        config.distribution_schedule,
+        vec![RewardSchedule {
+            id: 0,
+            start: 12345,
+            end: 12345 + 100,
+            amount: Uint128::from(1000000u128),
+            label: None,
+            curve: EmissionCurve::Linear,
+        }]
+    );
+
+    // the single pool becomes the default pool
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Pool {
+            pool_id: 0,
+            block_height: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<PoolResponse>(&res).unwrap(),
+        PoolResponse {
+            pool_id: 0,
+            staking_token: AssetInfo::Token {
+                contract_addr: "staking0000".to_string(),
+            },
+            alloc_point: 1,
+            last_distributed: 12345 + 10,
+            total_bond_amount: Uint128::from(100u128),
+            global_reward_index: Decimal::from_ratio(1000u128, 1u128),
+        }
+    );
+
+    let query_state = |deps: Deps| -> StateResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::State { block_height: None }).unwrap())
+            .unwrap()
+    };
+    let state = query_state(deps.as_ref());
+    assert_eq!(state.total_bond_amount, Uint128::from(100u128));
+    assert_eq!(
+        state.global_reward_index,
+        Decimal::from_ratio(1000u128, 1u128)
+    );
+    assert_eq!(state.total_distributed, Uint128::zero());
+
+    // the stakers not migrated yet are read from the single pool
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::StakerInfo {
+            staker: "addr0000".to_string(),
+            pool_id: None,
+            block_height: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<StakerInfoResponse>(&res).unwrap(),
+        StakerInfoResponse {
+            staker: "addr0000".to_string(),
+            pool_id: 0,
+            reward_index: Decimal::from_ratio(500u128, 1u128),
+            bond_amount: Uint128::from(60u128),
+            pending_reward: Uint128::from(10000u128),
+            pending_native_rewards: vec![],
+            total_claimed: Uint128::zero(),
+        }
+    );
+
+    // a staker bonding before its migration keeps the position
+    let info = mock_info("staking0000", &[]);
+    let _res = execute(deps.as_mut(), env.clone(), info, bond_msg("addr0001", 10)).unwrap();
+
+    // the stakers move to the default pool in batches, the rewards owed to them
+    // by the migration count as distributed on top of the 100,000 synced since
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::MigrateStakers { limit: Some(1) },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_stakers"),
+            attr("migrated", "1"),
+            attr("done", "false"),
+        ]
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::MigrateStakers { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate_stakers"),
+            attr("migrated", "1"),
+            attr("done", "true"),
+        ]
+    );
+    assert_eq!(
+        query_state(deps.as_ref()).total_distributed,
+        Uint128::from(140000u128)
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::MigrateStakers { limit: None },
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "no stakers left to migrate"),
+        _ => panic!("Must return generic error"),
+    }
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TopStakers {
+            pool_id: None,
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let top_stakers: TopStakersResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        top_stakers
+            .stakers
+            .iter()
+            .map(|s| (s.staker.as_str(), s.bond_amount.u128()))
+            .collect::<Vec<_>>(),
+        vec![("addr0000", 60), ("addr0001", 50)]
+    );
+
+    // the distribution goes on from the last distributed block
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Pool {
+            pool_id: 0,
+            block_height: Some(env.block.height),
+        },
+    )
+    .unwrap();
+    let pool: PoolResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pool.global_reward_index,
+        Decimal::from_ratio(2000u128, 1u128)
+    );
+
+    // migrating again leaves the contract as it is
+    let _res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    assert_eq!(from_binary::<ConfigResponse>(&res).unwrap(), config);
+}
+
+// single 1,000,000 reward schedule over blocks 12345..12445 for the staking0000 pool
+fn instantiate_single_schedule(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    let msg = InstantiateMsg {
+        xdefi_token: "reward0000".to_string(),
+        staking_token: AssetInfo::Token {
+            contract_addr: "staking0000".to_string(),
+        },
+        distribution_schedule: vec![(12345, 12345 + 100, Uint128::from(1000000u128))],
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+// cw20 hook bonding staking0000 tokens into the default pool, sent by the staking token
+fn bond_msg(staker: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: staker.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            pool_id: None,
+            staker: None,
+        })
+        .unwrap(),
+    })
+}
+
+// `instantiate_single_schedule` with 100 tokens bonded by addr0000 at the first block
+fn instantiate_and_bond(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> Env {
+    instantiate_single_schedule(deps);
+
+    let info = mock_info("staking0000", &[]);
+    let env = mock_env();
+    let _res = execute(deps.as_mut(), env.clone(), info, bond_msg("addr0000", 100)).unwrap();
+
+    env
+}